@@ -1,10 +1,23 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
+use futures_util::TryStreamExt;
 use reqwest::header;
 use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    sync::mpsc::{self, UnboundedReceiver},
+    time::sleep,
+};
+use tokio_util::io::StreamReader;
 
-use crate::config::ResolvedAiProvider;
+use crate::config::{ProviderProtocol, ResolvedAiProvider};
+
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
 
 const DEFAULT_SYSTEM_PROMPT: &str = "You are an analyst for real-time Twitter monitoring. Provide concise, practical analysis based on the user's request.";
 const DEFAULT_MONITOR_PROMPT: &str = "Summarize why this post matters and what to watch next.";
@@ -14,22 +27,64 @@ const USER_PROMPT_TEMPLATE: &str = "\
 Twitter post:
 {{post_text}}";
 
+/// Cache key for per-provider reqwest clients: clients only differ by proxy and
+/// timeout, so providers sharing both reuse the same connection pool.
+type ClientKey = (Option<String>, u64);
+
 #[derive(Debug, Clone)]
 pub struct AiClient {
     http: reqwest::Client,
+    clients: Arc<Mutex<HashMap<ClientKey, reqwest::Client>>>,
 }
 
-#[derive(Debug, Serialize)]
-struct ChatCompletionRequest {
-    model: String,
-    temperature: f32,
-    messages: Vec<ChatMessage>,
+/// Hard cap on tool-calling round-trips so a model that keeps asking for tools
+/// can never loop forever.
+const MAX_TOOL_ITERATIONS: usize = 5;
+
+/// One in-flight tool-calling round-trip: the calls the model wants to make
+/// (empty once it's ready to answer) and the final text, if any.
+struct ToolRoundResult {
+    /// The assistant's turn, in the provider's own wire shape, to push back
+    /// onto the running `messages` array unmodified before the tool results.
+    assistant_message: serde_json::Value,
+    calls: Option<Vec<ToolCallRequest>>,
+    text: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
+/// A single tool call the model asked for, normalized across providers'
+/// differing wire shapes (OpenAI's `function.arguments` JSON string vs.
+/// Anthropic's `input` object).
+struct ToolCallRequest {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ToolCall {
+    id: String,
+    function: ToolCallFunction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ToolCallFunction {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+/// A set of callable functions the analyst can invoke to pull extra post context
+/// (parent tweets, author timelines, …) before producing its final answer.
+pub trait ToolRegistry: Send + Sync {
+    /// OpenAI-style `tools` definitions advertised to the model.
+    fn definitions(&self) -> Vec<serde_json::Value>;
+
+    /// Dispatch a single tool call, returning the JSON result as a string.
+    fn dispatch(
+        &self,
+        name: &str,
+        arguments: &str,
+    ) -> impl std::future::Future<Output = Result<String>> + Send;
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,6 +101,8 @@ struct ChatChoice {
 #[derive(Debug, Deserialize)]
 struct ChatOutputMessage {
     content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCall>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,6 +111,571 @@ struct ChatApiError {
     r#type: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ChatStreamChunk {
+    choices: Option<Vec<ChatStreamChoice>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatStreamChoice {
+    delta: ChatStreamDelta,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatStreamDelta {
+    content: Option<String>,
+}
+
+const DEFAULT_TEMPERATURE: f32 = 0.2;
+const DEFAULT_ANTHROPIC_MAX_TOKENS: u32 = 1024;
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Optional generation knobs resolved from provider/monitor config. `None`
+/// fields are left out of the request body so each provider keeps its own
+/// default, except the OpenAI temperature, which falls back to [`DEFAULT_TEMPERATURE`],
+/// and the Anthropic `max_tokens`, which falls back to [`DEFAULT_ANTHROPIC_MAX_TOKENS`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GenerationParams {
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub top_p: Option<f32>,
+}
+
+impl GenerationParams {
+    fn from_provider(provider: &ResolvedAiProvider) -> Self {
+        Self {
+            temperature: provider.temperature,
+            max_tokens: provider.max_tokens,
+            top_p: provider.top_p,
+        }
+    }
+}
+
+/// Request/response shape for one provider wire protocol. Selected per provider
+/// so [`AiClient::analyze_post`] stays agnostic to whether it's talking to an
+/// OpenAI-compatible endpoint or a native Anthropic one.
+trait ProviderAdapter {
+    fn endpoint(&self, base_url: &str) -> String;
+    fn auth_headers(&self, api_key: &str) -> Result<header::HeaderMap>;
+    fn build_body(
+        &self,
+        system: &str,
+        user: &str,
+        model: &str,
+        params: GenerationParams,
+    ) -> serde_json::Value;
+    fn parse_output(&self, body: &str) -> Result<String>;
+
+    /// Same request as [`Self::build_body`] with streaming turned on.
+    fn build_stream_body(
+        &self,
+        system: &str,
+        user: &str,
+        model: &str,
+        params: GenerationParams,
+    ) -> serde_json::Value {
+        let mut body = self.build_body(system, user, model, params);
+        body["stream"] = serde_json::json!(true);
+        body
+    }
+
+    /// Parse one `data:`-prefixed SSE payload, returning the text fragment to
+    /// forward (if any). `Ok(None)` means the event carried no text (e.g. a
+    /// `message_start`/`ping` event) and should just be skipped.
+    fn parse_stream_chunk(&self, payload: &str) -> Result<Option<String>>;
+
+    /// Build one tool-calling round's request body. `messages` are raw,
+    /// already-serialized turns in this provider's own wire shape (built up
+    /// from this trait's own [`Self::parse_tool_response`]/
+    /// [`Self::tool_result_message`] output), so each adapter only needs to
+    /// wrap them with `system`/`model`/`tools`/generation params.
+    fn build_tool_body(
+        &self,
+        system: &str,
+        model: &str,
+        params: GenerationParams,
+        tools: &[serde_json::Value],
+        messages: &[serde_json::Value],
+    ) -> serde_json::Value;
+
+    /// Parse a tool-calling round's response into the assistant turn to echo
+    /// back, any tool calls the model wants made, and the final text (set
+    /// once `calls` is empty).
+    fn parse_tool_response(&self, body: &str) -> Result<ToolRoundResult>;
+
+    /// Build the message that reports one tool's result back to the model.
+    fn tool_result_message(&self, call_id: &str, content: &str) -> serde_json::Value;
+}
+
+fn adapter_for(protocol: ProviderProtocol) -> Box<dyn ProviderAdapter> {
+    match protocol {
+        ProviderProtocol::OpenAiChat => Box::new(OpenAiChatAdapter),
+        ProviderProtocol::Anthropic => Box::new(AnthropicAdapter),
+    }
+}
+
+struct OpenAiChatAdapter;
+
+impl ProviderAdapter for OpenAiChatAdapter {
+    fn endpoint(&self, base_url: &str) -> String {
+        format!("{}/chat/completions", base_url.trim_end_matches('/'))
+    }
+
+    fn auth_headers(&self, api_key: &str) -> Result<header::HeaderMap> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {api_key}").parse()?);
+        headers.insert(header::CONTENT_TYPE, "application/json".parse()?);
+        Ok(headers)
+    }
+
+    fn build_body(
+        &self,
+        system: &str,
+        user: &str,
+        model: &str,
+        params: GenerationParams,
+    ) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": model,
+            "temperature": params.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+            "messages": [
+                {"role": "system", "content": system},
+                {"role": "user", "content": user},
+            ],
+        });
+        if let Some(max_tokens) = params.max_tokens {
+            body["max_tokens"] = max_tokens.into();
+        }
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        body
+    }
+
+    fn parse_output(&self, body: &str) -> Result<String> {
+        let value = serde_json::from_str::<serde_json::Value>(body)
+            .with_context(|| format!("failed to parse ai response: {body}"))?;
+        if let Some(error) = value.get("error") {
+            anyhow::bail!("ai api error: {}", render_api_error(error));
+        }
+        value
+            .pointer("/choices/0/message/content")
+            .and_then(|content| content.as_str())
+            .map(str::trim)
+            .filter(|content| !content.is_empty())
+            .map(str::to_string)
+            .context("ai response did not contain a message")
+    }
+
+    fn parse_stream_chunk(&self, payload: &str) -> Result<Option<String>> {
+        let chunk = serde_json::from_str::<ChatStreamChunk>(payload)
+            .with_context(|| format!("failed to parse ai stream chunk: {payload}"))?;
+        Ok(chunk
+            .choices
+            .and_then(|choices| choices.into_iter().next())
+            .and_then(|choice| choice.delta.content))
+    }
+
+    fn build_tool_body(
+        &self,
+        system: &str,
+        model: &str,
+        params: GenerationParams,
+        tools: &[serde_json::Value],
+        messages: &[serde_json::Value],
+    ) -> serde_json::Value {
+        let mut all_messages = vec![serde_json::json!({"role": "system", "content": system})];
+        all_messages.extend(messages.iter().cloned());
+
+        let mut body = serde_json::json!({
+            "model": model,
+            "temperature": params.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+            "messages": all_messages,
+            "stream": false,
+        });
+        if let Some(max_tokens) = params.max_tokens {
+            body["max_tokens"] = max_tokens.into();
+        }
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        if !tools.is_empty() {
+            body["tools"] = serde_json::json!(tools);
+        }
+        body
+    }
+
+    fn parse_tool_response(&self, body: &str) -> Result<ToolRoundResult> {
+        let parsed = serde_json::from_str::<ChatCompletionResponse>(body)
+            .with_context(|| format!("failed to parse ai response: {body}"))?;
+        if let Some(api_error) = parsed.error {
+            anyhow::bail!("ai api error: {}", render_chat_api_error(&api_error));
+        }
+
+        let message = parsed
+            .choices
+            .and_then(|choices| choices.into_iter().next())
+            .map(|choice| choice.message);
+
+        let tool_calls = message.as_ref().and_then(|message| message.tool_calls.clone());
+        let text = message
+            .as_ref()
+            .and_then(|message| message.content.clone())
+            .map(|content| content.trim().to_string())
+            .filter(|content| !content.is_empty());
+
+        let calls = tool_calls.clone().filter(|calls| !calls.is_empty()).map(|calls| {
+            calls
+                .into_iter()
+                .map(|call| ToolCallRequest {
+                    id: call.id,
+                    name: call.function.name,
+                    arguments: call.function.arguments,
+                })
+                .collect()
+        });
+
+        let assistant_message = serde_json::json!({
+            "role": "assistant",
+            "content": message.and_then(|message| message.content),
+            "tool_calls": tool_calls,
+        });
+
+        Ok(ToolRoundResult {
+            assistant_message,
+            calls,
+            text,
+        })
+    }
+
+    fn tool_result_message(&self, call_id: &str, content: &str) -> serde_json::Value {
+        serde_json::json!({
+            "role": "tool",
+            "tool_call_id": call_id,
+            "content": content,
+        })
+    }
+}
+
+struct AnthropicAdapter;
+
+impl ProviderAdapter for AnthropicAdapter {
+    fn endpoint(&self, base_url: &str) -> String {
+        format!("{}/messages", base_url.trim_end_matches('/'))
+    }
+
+    fn auth_headers(&self, api_key: &str) -> Result<header::HeaderMap> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert("x-api-key", api_key.parse()?);
+        headers.insert("anthropic-version", ANTHROPIC_VERSION.parse()?);
+        headers.insert(header::CONTENT_TYPE, "application/json".parse()?);
+        Ok(headers)
+    }
+
+    fn build_body(
+        &self,
+        system: &str,
+        user: &str,
+        model: &str,
+        params: GenerationParams,
+    ) -> serde_json::Value {
+        // Anthropic lifts the system prompt out of `messages` into a top-level
+        // field and always requires `max_tokens`.
+        let mut body = serde_json::json!({
+            "model": model,
+            "system": system,
+            "max_tokens": params.max_tokens.unwrap_or(DEFAULT_ANTHROPIC_MAX_TOKENS),
+            "messages": [
+                {"role": "user", "content": user},
+            ],
+        });
+        if let Some(temperature) = params.temperature {
+            body["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        body
+    }
+
+    fn parse_output(&self, body: &str) -> Result<String> {
+        let value = serde_json::from_str::<serde_json::Value>(body)
+            .with_context(|| format!("failed to parse ai response: {body}"))?;
+        if let Some(error) = value.get("error") {
+            anyhow::bail!("ai api error: {}", render_api_error(error));
+        }
+        value
+            .get("content")
+            .and_then(|content| content.as_array())
+            .and_then(|blocks| {
+                blocks.iter().find_map(|block| {
+                    match block.get("type").and_then(|kind| kind.as_str()) {
+                        Some("text") => block.get("text").and_then(|text| text.as_str()),
+                        _ => None,
+                    }
+                })
+            })
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+            .context("ai response did not contain a message")
+    }
+
+    fn parse_stream_chunk(&self, payload: &str) -> Result<Option<String>> {
+        let event = serde_json::from_str::<AnthropicStreamEvent>(payload)
+            .with_context(|| format!("failed to parse ai stream chunk: {payload}"))?;
+
+        if event.kind == "error" {
+            let message = event
+                .error
+                .map(|error| render_chat_api_error(&error))
+                .unwrap_or_else(|| "unknown api error".to_string());
+            anyhow::bail!("ai api error: {message}");
+        }
+
+        Ok(event.delta.and_then(|delta| delta.text))
+    }
+
+    fn build_tool_body(
+        &self,
+        system: &str,
+        model: &str,
+        params: GenerationParams,
+        tools: &[serde_json::Value],
+        messages: &[serde_json::Value],
+    ) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": model,
+            "system": system,
+            "max_tokens": params.max_tokens.unwrap_or(DEFAULT_ANTHROPIC_MAX_TOKENS),
+            "messages": messages,
+        });
+        if let Some(temperature) = params.temperature {
+            body["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = params.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        if !tools.is_empty() {
+            body["tools"] = serde_json::json!(anthropic_tool_defs(tools));
+        }
+        body
+    }
+
+    fn parse_tool_response(&self, body: &str) -> Result<ToolRoundResult> {
+        let parsed = serde_json::from_str::<AnthropicMessageResponse>(body)
+            .with_context(|| format!("failed to parse ai response: {body}"))?;
+        if let Some(error) = parsed.error {
+            anyhow::bail!("ai api error: {}", render_chat_api_error(&error));
+        }
+
+        let mut calls = Vec::new();
+        let mut text_parts = Vec::new();
+        for block in &parsed.content {
+            match block.get("type").and_then(|kind| kind.as_str()) {
+                Some("tool_use") => {
+                    let id = block.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+                    let name = block.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+                    let arguments = block.get("input").cloned().unwrap_or_else(|| serde_json::json!({}));
+                    calls.push(ToolCallRequest {
+                        id: id.to_string(),
+                        name: name.to_string(),
+                        arguments: arguments.to_string(),
+                    });
+                }
+                Some("text") => {
+                    if let Some(text) = block.get("text").and_then(|v| v.as_str()) {
+                        let text = text.trim();
+                        if !text.is_empty() {
+                            text_parts.push(text.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let assistant_message = serde_json::json!({
+            "role": "assistant",
+            "content": parsed.content,
+        });
+
+        Ok(ToolRoundResult {
+            assistant_message,
+            calls: (!calls.is_empty()).then_some(calls),
+            text: (!text_parts.is_empty()).then(|| text_parts.join("\n\n")),
+        })
+    }
+
+    fn tool_result_message(&self, call_id: &str, content: &str) -> serde_json::Value {
+        serde_json::json!({
+            "role": "user",
+            "content": [{"type": "tool_result", "tool_use_id": call_id, "content": content}],
+        })
+    }
+}
+
+/// Anthropic's `tools` entries are `{name, description, input_schema}`; map
+/// from the `{type: "function", function: {name, description, parameters}}`
+/// shape [`ToolRegistry::definitions`] advertises.
+fn anthropic_tool_defs(tools: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    tools
+        .iter()
+        .filter_map(|tool| {
+            let function = tool.get("function")?;
+            Some(serde_json::json!({
+                "name": function.get("name")?.as_str()?,
+                "description": function.get("description").and_then(|d| d.as_str()).unwrap_or(""),
+                "input_schema": function
+                    .get("parameters")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!({"type": "object", "properties": {}})),
+            }))
+        })
+        .collect()
+}
+
+/// One non-streaming Anthropic `/messages` response, kept as raw content
+/// blocks so [`AnthropicAdapter::parse_tool_response`] can both scan them and
+/// echo them back verbatim as the next assistant turn.
+#[derive(Debug, Deserialize)]
+struct AnthropicMessageResponse {
+    #[serde(default)]
+    content: Vec<serde_json::Value>,
+    #[serde(default)]
+    error: Option<ChatApiError>,
+}
+
+/// One Anthropic streaming SSE payload. Only `content_block_delta` events carry
+/// text; `message_start`/`content_block_start`/`message_delta`/`message_stop`/
+/// `ping` are structurally valid but have no `delta.text` and are skipped.
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    delta: Option<AnthropicStreamDelta>,
+    #[serde(default)]
+    error: Option<ChatApiError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// Flatten an API error object into `"type: message"` (or just one of them).
+fn render_api_error(error: &serde_json::Value) -> String {
+    let message = error.get("message").and_then(|m| m.as_str());
+    let kind = error.get("type").and_then(|t| t.as_str());
+    match (kind, message) {
+        (Some(kind), Some(message)) => format!("{kind}: {message}"),
+        (None, Some(message)) => message.to_string(),
+        (Some(kind), None) => kind.to_string(),
+        (None, None) => error.to_string(),
+    }
+}
+
+/// Same as [`render_api_error`], for the typed [`ChatApiError`] shape shared
+/// by both providers' non-streaming error bodies.
+fn render_chat_api_error(error: &ChatApiError) -> String {
+    match (&error.r#type, &error.message) {
+        (Some(kind), Some(message)) => format!("{kind}: {message}"),
+        (None, Some(message)) => message.clone(),
+        (Some(kind), None) => kind.clone(),
+        (None, None) => "unknown api error".to_string(),
+    }
+}
+
+/// Retry budget for transient AI failures.
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    retries: u32,
+    base_delay: Duration,
+}
+
+impl RetryPolicy {
+    fn from_provider(provider: &ResolvedAiProvider) -> Self {
+        Self {
+            retries: provider.retries,
+            base_delay: Duration::from_millis(provider.retry_base_ms),
+        }
+    }
+}
+
+/// POST a pre-built JSON body and return the raw response text. Transient
+/// failures (HTTP 429 and 5xx) are retried up to `policy.retries` times with
+/// exponential backoff; a `Retry-After` header overrides the computed delay.
+/// Any other non-success status fails immediately.
+async fn post_json(
+    http: &reqwest::Client,
+    endpoint: &str,
+    headers: header::HeaderMap,
+    body: serde_json::Value,
+    policy: RetryPolicy,
+) -> Result<String> {
+    let mut attempt = 0;
+    loop {
+        let response = http
+            .post(endpoint)
+            .headers(headers.clone())
+            .json(&body)
+            .send()
+            .await
+            .context("failed to call ai endpoint")?;
+
+        let status = response.status();
+        let retry_after = parse_retry_after(response.headers());
+        let text = response
+            .text()
+            .await
+            .context("failed to read ai response body")?;
+
+        if status.is_success() {
+            return Ok(text);
+        }
+
+        let code = status.as_u16();
+        let retryable = code == 429 || status.is_server_error();
+        if retryable && attempt < policy.retries {
+            let delay = retry_after.unwrap_or_else(|| backoff_delay(policy.base_delay, attempt));
+            sleep(delay).await;
+            attempt += 1;
+            continue;
+        }
+
+        anyhow::bail!("ai request failed ({status}): {text}");
+    }
+}
+
+/// Exponential backoff with a small additive jitter so retries from concurrent
+/// analyses don't all fire on the same tick.
+fn backoff_delay(base: Duration, attempt: u32) -> Duration {
+    let scaled = base.saturating_mul(1u32 << attempt.min(16));
+    scaled + jitter(base)
+}
+
+/// Up to ~`base`/4 of pseudo-random jitter derived from the wall clock; avoids
+/// pulling in an RNG dependency for a non-cryptographic nudge.
+fn jitter(base: Duration) -> Duration {
+    let span = (base.as_millis() / 4).max(1) as u64;
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as u64)
+        .unwrap_or(0);
+    Duration::from_millis(nanos % span)
+}
+
+/// Parse a `Retry-After` header expressed as an integer number of seconds.
+fn parse_retry_after(headers: &header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
 fn render_user_prompt(template: &str, monitor_prompt: &str, post_text: &str) -> String {
     template
         .replace("{{monitor_prompt}}", monitor_prompt.trim())
@@ -73,11 +695,37 @@ pub fn prepare_prompts(prompt: &str, post_text: &str) -> (String, String, String
 
 impl AiClient {
     pub fn new() -> Result<Self> {
-        let http = reqwest::Client::builder()
-            .timeout(Duration::from_secs(60))
-            .build()
+        let http = build_http_client(None, DEFAULT_TIMEOUT_SECS)
             .context("failed to construct ai http client")?;
-        Ok(Self { http })
+        Ok(Self {
+            http,
+            clients: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Return a reqwest client configured with `provider`'s proxy and timeout,
+    /// constructing and caching one per unique `(proxy, timeout)` pair. Falls
+    /// back to the shared default client when the provider needs neither.
+    fn client_for(&self, provider: &ResolvedAiProvider) -> Result<reqwest::Client> {
+        let timeout = provider.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let proxy = provider
+            .proxy
+            .as_ref()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+
+        if proxy.is_none() && timeout == DEFAULT_TIMEOUT_SECS && !proxy_in_environment() {
+            return Ok(self.http.clone());
+        }
+
+        let key = (proxy.clone(), timeout);
+        if let Some(client) = self.clients.lock().unwrap().get(&key).cloned() {
+            return Ok(client);
+        }
+
+        let client = build_http_client(proxy.as_deref(), timeout)?;
+        self.clients.lock().unwrap().insert(key, client.clone());
+        Ok(client)
     }
 
     pub async fn analyze_post(
@@ -97,78 +745,207 @@ impl AiClient {
             anyhow::bail!("AI model ID is empty");
         }
 
-        let endpoint = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+        let adapter = adapter_for(provider.protocol);
+        let endpoint = adapter.endpoint(base_url);
 
         let (system_prompt, _monitor_prompt, user_prompt) = prepare_prompts(&prompt, &post_text);
+        let body = adapter.build_body(
+            &system_prompt,
+            &user_prompt,
+            &model,
+            GenerationParams::from_provider(&provider),
+        );
+        let headers = adapter.auth_headers(&provider.api_key)?;
 
-        let request = ChatCompletionRequest {
-            model,
-            temperature: 0.2,
-            messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: system_prompt,
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: user_prompt,
-                },
-            ],
+        let client = self.client_for(&provider)?;
+        let policy = RetryPolicy::from_provider(&provider);
+        let text = post_json(&client, &endpoint, headers, body, policy).await?;
+        adapter.parse_output(&text)
+    }
+
+    /// Analyze a post while letting the model call tools from `registry` to pull
+    /// extra context. Routed through the same [`ProviderAdapter`]/[`post_json`]
+    /// dispatch as [`Self::analyze_post`], so endpoint, auth, and retries match
+    /// the configured provider instead of assuming OpenAI. We re-POST after
+    /// each batch of tool calls, appending one tool-result message per call,
+    /// until the model returns a final text answer or [`MAX_TOOL_ITERATIONS`]
+    /// is reached.
+    pub async fn analyze_post_with_tools<R: ToolRegistry>(
+        &self,
+        provider: ResolvedAiProvider,
+        model: String,
+        prompt: String,
+        post_text: String,
+        registry: &R,
+    ) -> Result<String> {
+        let base_url = provider.base_url.trim();
+        if base_url.is_empty() {
+            anyhow::bail!("AI endpoint is empty");
+        }
+        let model = model.trim().to_string();
+        if model.is_empty() {
+            anyhow::bail!("AI model ID is empty");
+        }
+
+        let adapter = adapter_for(provider.protocol);
+        let endpoint = adapter.endpoint(base_url);
+        let headers = adapter.auth_headers(&provider.api_key)?;
+
+        let (system_prompt, _monitor_prompt, user_prompt) = prepare_prompts(&prompt, &post_text);
+        let client = self.client_for(&provider)?;
+        let policy = RetryPolicy::from_provider(&provider);
+        let params = GenerationParams::from_provider(&provider);
+        let tools = registry.definitions();
+
+        let mut messages = vec![serde_json::json!({"role": "user", "content": user_prompt})];
+
+        for _ in 0..MAX_TOOL_ITERATIONS {
+            let body = adapter.build_tool_body(&system_prompt, &model, params, &tools, &messages);
+            let text = post_json(&client, &endpoint, headers.clone(), body, policy).await?;
+            let round = adapter.parse_tool_response(&text)?;
+
+            let Some(calls) = round.calls.filter(|calls| !calls.is_empty()) else {
+                return round.text.context("ai response did not contain a message");
+            };
+
+            // Echo the assistant's tool-call turn, then answer each call.
+            messages.push(round.assistant_message);
+            for call in calls {
+                let result = registry
+                    .dispatch(&call.name, &call.arguments)
+                    .await
+                    .unwrap_or_else(|error| {
+                        format!("{{\"error\":{}}}", serde_json::Value::from(error.to_string()))
+                    });
+                messages.push(adapter.tool_result_message(&call.id, &result));
+            }
+        }
+
+        anyhow::bail!("ai tool-calling loop exceeded {MAX_TOOL_ITERATIONS} iterations");
+    }
+
+    /// Stream an analysis as Server-Sent Events, forwarding each `delta.content`
+    /// fragment as it arrives so the TUI can update a single feed item in place.
+    ///
+    /// Providers that don't support SSE should fall back to [`Self::analyze_post`];
+    /// this path only sets `"stream": true` and parses `data:`-prefixed chunks.
+    pub fn analyze_post_stream(
+        &self,
+        provider: ResolvedAiProvider,
+        model: String,
+        prompt: String,
+        post_text: String,
+    ) -> UnboundedReceiver<Result<String>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let http = match self.client_for(&provider) {
+            Ok(http) => http,
+            Err(error) => {
+                let _ = tx.send(Err(error));
+                return rx;
+            }
         };
 
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::AUTHORIZATION,
-            format!("Bearer {}", provider.api_key).parse()?,
-        );
-        headers.insert(header::CONTENT_TYPE, "application/json".parse()?);
+        tokio::spawn(async move {
+            if let Err(error) = stream_analysis(&http, provider, model, prompt, post_text, &tx).await
+            {
+                let _ = tx.send(Err(error));
+            }
+        });
 
-        let response = self
-            .http
-            .post(endpoint)
-            .headers(headers)
-            .json(&request)
-            .send()
-            .await
-            .context("failed to call ai endpoint")?;
+        rx
+    }
+}
 
-        let status = response.status();
+async fn stream_analysis(
+    http: &reqwest::Client,
+    provider: ResolvedAiProvider,
+    model: String,
+    prompt: String,
+    post_text: String,
+    tx: &mpsc::UnboundedSender<Result<String>>,
+) -> Result<()> {
+    let base_url = provider.base_url.trim();
+    if base_url.is_empty() {
+        anyhow::bail!("AI endpoint is empty");
+    }
+
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        anyhow::bail!("AI model ID is empty");
+    }
+
+    let adapter = adapter_for(provider.protocol);
+    let endpoint = adapter.endpoint(base_url);
+    let (system_prompt, _monitor_prompt, user_prompt) = prepare_prompts(&prompt, &post_text);
+    let params = GenerationParams::from_provider(&provider);
+    let body = adapter.build_stream_body(&system_prompt, &user_prompt, &model, params);
+    let headers = adapter.auth_headers(&provider.api_key)?;
+
+    let response = http
+        .post(endpoint)
+        .headers(headers)
+        .json(&body)
+        .send()
+        .await
+        .context("failed to call ai endpoint")?;
+
+    let status = response.status();
+    if !status.is_success() {
         let body = response
             .text()
             .await
             .context("failed to read ai response body")?;
+        anyhow::bail!("ai request failed ({status}): {body}");
+    }
 
-        if !status.is_success() {
-            anyhow::bail!("ai request failed ({status}): {body}");
-        }
+    // The SSE body is newline-delimited; events are separated by a blank line
+    // and each payload line carries a `data: ` prefix.
+    let byte_stream = response.bytes_stream().map_err(std::io::Error::other);
+    let reader = StreamReader::new(byte_stream);
+    let mut lines = BufReader::new(reader).lines();
 
-        let parsed = serde_json::from_str::<ChatCompletionResponse>(&body)
-            .with_context(|| format!("failed to parse ai response: {body}"))?;
+    while let Some(line) = lines.next_line().await.context("failed to read ai stream")? {
+        let Some(payload) = line.trim().strip_prefix("data:") else {
+            continue;
+        };
+        let payload = payload.trim();
+        if payload.is_empty() {
+            continue;
+        }
+        if payload == "[DONE]" {
+            break;
+        }
 
-        if let Some(api_error) = parsed.error {
-            let mut parts = Vec::new();
-            if let Some(kind) = api_error.r#type {
-                parts.push(kind);
-            }
-            if let Some(message) = api_error.message {
-                parts.push(message);
+        if let Some(content) = adapter.parse_stream_chunk(payload)? {
+            if !content.is_empty() && tx.send(Ok(content)).is_err() {
+                break;
             }
-            let rendered = if parts.is_empty() {
-                "unknown api error".to_string()
-            } else {
-                parts.join(": ")
-            };
-            anyhow::bail!("ai api error: {rendered}");
         }
+    }
+
+    Ok(())
+}
 
-        let output = parsed
-            .choices
-            .and_then(|choices| choices.into_iter().next())
-            .and_then(|choice| choice.message.content)
-            .map(|content| content.trim().to_string())
-            .filter(|content| !content.is_empty())
-            .context("ai response did not contain a message")?;
 
-        Ok(output)
+/// Build a reqwest client with an optional proxy and a request timeout. When no
+/// explicit proxy is given reqwest still honors the standard `HTTPS_PROXY` /
+/// `ALL_PROXY` environment variables.
+fn build_http_client(proxy: Option<&str>, timeout_secs: u64) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().timeout(Duration::from_secs(timeout_secs));
+
+    if let Some(proxy) = proxy.map(str::trim).filter(|value| !value.is_empty()) {
+        let proxy = reqwest::Proxy::all(proxy)
+            .with_context(|| format!("invalid proxy url '{proxy}'"))?;
+        builder = builder.proxy(proxy);
     }
+
+    builder.build().context("failed to build ai http client")
+}
+
+/// Whether one of the standard proxy environment variables is set, so we avoid
+/// handing out the shared default client when the environment implies a proxy.
+fn proxy_in_environment() -> bool {
+    ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .any(|key| std::env::var(key).map(|v| !v.trim().is_empty()).unwrap_or(false))
 }
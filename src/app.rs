@@ -1,25 +1,38 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use anyhow::{Context, Result};
-use chrono::Local;
-use serde_json::to_string_pretty;
+use anyhow::Result;
+use chrono::{Local, Utc};
+use ratatui::text::Line;
 use uuid::Uuid;
 
 use crate::{
-    config::AppConfig,
+    clipboard::ClipboardProvider,
+    config::{AppConfig, ForwarderConfig, PromptTemplate},
+    feed_store::FeedStore,
+    fuzzy::{self, FuzzyMatch},
+    keymap::Keymap,
     models::{
-        AnalysisSettings, FeedItem, FeedKind, Monitor, MonitorKind, MonitorStore, StreamPost,
-        build_query, parse_account_handles,
+        AnalysisSettings, FeedItem, FeedKind, LifecycleEvent, Monitor, MonitorKind, StreamPost,
+        VOLUME_BUCKETS, VolumeSeries, build_query, parse_account_handles,
     },
+    notifications::NotificationCenter,
+    scripting::ScriptEngine,
+    store::MonitorStore,
     target_files::{TargetFileEntry, load_target_file_entries},
+    theme::Theme,
+    yaml_syntax,
 };
 
 const MAX_FEED_ITEMS: usize = 500;
 
+/// Returned by [`App::monitor_volume`] for a monitor with no recorded
+/// activity yet, so callers can always render a full-width sparkline.
+const EMPTY_VOLUME: [u16; VOLUME_BUCKETS] = [0; VOLUME_BUCKETS];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FocusPane {
     Monitors,
@@ -45,6 +58,29 @@ pub struct AddMonitorForm {
     pub ai_endpoint: String,
     pub ai_api_key: String,
     pub ai_prompt: String,
+    pub ai_temperature: Option<f32>,
+    pub ai_max_tokens: Option<u32>,
+    pub ai_top_p: Option<f32>,
+    /// Fire a desktop notification when an analysis completes.
+    pub ai_notify: bool,
+    /// Let the model call tools before answering, loaded from a target
+    /// file's `ai.use_tools:`/`ai_use_tools:` field. Not exposed as a form
+    /// field; only settable by importing a target file.
+    pub ai_use_tools: bool,
+    /// Fire a desktop notification when a post matches this monitor.
+    pub notify: bool,
+    /// External command to run when a post matches this monitor, loaded from
+    /// a target file's `on_match:` field. Not exposed as a form field; only
+    /// settable by importing a target file.
+    pub on_match: Option<String>,
+    /// Webhook sinks overriding `AppConfig.forwarders` for this monitor,
+    /// loaded from a target file's `sinks:` field. Not exposed as a form
+    /// field; only settable by importing a target file.
+    pub sinks: Option<Vec<ForwarderConfig>>,
+    /// Lua script overriding `AppConfig.lua_script` for this monitor, loaded
+    /// from a target file's `script:` field. Not exposed as a form field;
+    /// only settable by importing a target file.
+    pub script: Option<PathBuf>,
 }
 
 impl AddMonitorForm {
@@ -66,6 +102,15 @@ impl AddMonitorForm {
             ai_endpoint: String::new(),
             ai_api_key: String::new(),
             ai_prompt: "Summarize why this post matters and what to watch next.".to_string(),
+            ai_temperature: None,
+            ai_max_tokens: None,
+            ai_top_p: None,
+            ai_notify: false,
+            ai_use_tools: false,
+            notify: false,
+            on_match: None,
+            sinks: None,
+            script: None,
         };
         form.apply_provider_defaults(config, provider_names);
         form
@@ -93,6 +138,15 @@ impl AddMonitorForm {
             ai_endpoint: monitor.analysis.endpoint.clone(),
             ai_api_key: monitor.analysis.api_key.clone(),
             ai_prompt: monitor.analysis.prompt.clone(),
+            ai_temperature: monitor.analysis.temperature,
+            ai_max_tokens: monitor.analysis.max_tokens,
+            ai_top_p: monitor.analysis.top_p,
+            ai_notify: monitor.analysis.notify,
+            ai_use_tools: monitor.analysis.use_tools,
+            notify: monitor.notify,
+            on_match: monitor.on_match.clone(),
+            sinks: monitor.sinks.clone(),
+            script: monitor.script.clone(),
         }
     }
 
@@ -133,16 +187,17 @@ impl AddMonitorForm {
     }
 
     pub fn cycle_kind(&mut self, delta: i32) {
-        self.kind = match (self.kind.clone(), delta.signum()) {
-            (MonitorKind::Account, d) if d >= 0 => MonitorKind::Phrase,
-            (MonitorKind::Phrase, d) if d >= 0 => MonitorKind::Account,
-            (MonitorKind::Account, _) => MonitorKind::Phrase,
-            (MonitorKind::Phrase, _) => MonitorKind::Account,
-        };
+        const KINDS: [MonitorKind; 3] = [MonitorKind::Account, MonitorKind::Phrase, MonitorKind::Rss];
+        let index = KINDS
+            .iter()
+            .position(|kind| *kind == self.kind)
+            .unwrap_or(0) as i32;
+        let next = (index + delta.signum()).rem_euclid(KINDS.len() as i32);
+        self.kind = KINDS[next as usize].clone();
     }
 
     pub fn move_field(&mut self, delta: i32) {
-        let count = 10i32;
+        let count = 12i32;
         let next = (self.field_index as i32 + delta).rem_euclid(count);
         self.field_index = next as usize;
     }
@@ -166,6 +221,13 @@ impl AddMonitorForm {
                 let trimmed = self.target.trim().to_string();
                 (trimmed.clone(), trimmed)
             }
+            MonitorKind::Rss => {
+                let trimmed = self.target.trim().to_string();
+                if trimmed.is_empty() {
+                    anyhow::bail!("feed URL cannot be empty");
+                }
+                (trimmed.clone(), trimmed)
+            }
         };
 
         let label = if self.display_name.trim().is_empty() {
@@ -185,6 +247,11 @@ impl AddMonitorForm {
             endpoint: self.ai_endpoint.trim().to_string(),
             api_key: self.ai_api_key.trim().to_string(),
             prompt: self.ai_prompt.trim().to_string(),
+            temperature: self.ai_temperature,
+            max_tokens: self.ai_max_tokens,
+            top_p: self.ai_top_p,
+            notify: self.ai_notify,
+            use_tools: self.ai_use_tools,
         };
 
         if analysis.enabled {
@@ -209,6 +276,10 @@ impl AddMonitorForm {
             query,
             rule_tag: format!("xmon:{}", id.simple()),
             analysis,
+            notify: self.notify,
+            on_match: self.on_match.clone(),
+            sinks: self.sinks.clone(),
+            script: self.script.clone(),
         })
     }
 }
@@ -223,6 +294,10 @@ pub struct PendingMonitor {
     pub query: String,
     pub rule_tag: String,
     pub analysis: AnalysisSettings,
+    pub notify: bool,
+    pub on_match: Option<String>,
+    pub sinks: Option<Vec<ForwarderConfig>>,
+    pub script: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -235,6 +310,75 @@ pub struct TargetFilePicker {
     pub directory: PathBuf,
     pub entries: Vec<TargetFileEntry>,
     pub selected: usize,
+    /// Syntax-highlighted preview lines per entry path, keyed alongside the
+    /// mtime they were tokenized from so an on-disk change invalidates them.
+    highlight_cache: HashMap<PathBuf, (Option<SystemTime>, Vec<Line<'static>>)>,
+    pub preview_mode: PreviewMode,
+}
+
+/// Which view the target-file picker's right-hand pane renders, cycled with
+/// Ctrl+t.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreviewMode {
+    /// The annotated YAML source, as today.
+    #[default]
+    Source,
+    /// A key/value summary of the resolved effective settings, defaults
+    /// filled in.
+    Summary,
+    /// `entry.raw` diffed against the last version successfully applied to a
+    /// monitor through this picker.
+    Diff,
+}
+
+impl PreviewMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Source => Self::Summary,
+            Self::Summary => Self::Diff,
+            Self::Diff => Self::Source,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Source => "Source",
+            Self::Summary => "Effective settings",
+            Self::Diff => "Diff vs. last applied",
+        }
+    }
+}
+
+/// Cached fuzzy-match results for the filter overlay, keyed by the query they
+/// were computed for so navigation (which doesn't change the query) never
+/// recomputes scores.
+#[derive(Debug, Clone)]
+struct FilterCache {
+    query: String,
+    matches: Vec<(usize, FuzzyMatch)>,
+}
+
+/// A modal fuzzy-filter over whichever pane was focused when it was opened.
+/// `pane` is fixed for the life of the overlay; `selected` indexes into the
+/// current `matches`, not the underlying monitor/feed list.
+#[derive(Debug, Clone)]
+pub struct FilterOverlay {
+    pub pane: FocusPane,
+    pub query: String,
+    pub selected: usize,
+    cache: Option<FilterCache>,
+}
+
+/// Modal browser over the config's prompt library, opened from the add/edit
+/// modal's AI prompt field. Reuses the list+preview layout of
+/// [`TargetFilePicker`]. `save_name_input` is `Some` while the "save current
+/// prompt as..." sub-mode is collecting a name for the form's in-progress
+/// prompt text.
+#[derive(Debug, Clone)]
+pub struct PromptPicker {
+    pub prompts: Vec<PromptTemplate>,
+    pub selected: usize,
+    pub save_name_input: Option<String>,
 }
 
 #[derive(Debug)]
@@ -248,69 +392,156 @@ pub struct App {
     pub add_form: Option<AddMonitorForm>,
     pub edit_session: Option<EditSession>,
     pub target_file_picker: Option<TargetFilePicker>,
+    pub filter_overlay: Option<FilterOverlay>,
+    pub prompt_picker: Option<PromptPicker>,
     pub status: String,
     pub provider_names: Vec<String>,
     stream_connected: bool,
     monitor_activity: HashMap<Uuid, bool>,
     monitor_initiating: HashSet<Uuid>,
-    state_path: PathBuf,
+    store: MonitorStore,
+    feed_store: FeedStore,
+    config_path: PathBuf,
     pub config: AppConfig,
+    pub theme: Theme,
+    /// Raw YAML last successfully applied to a monitor from each target
+    /// file, keyed by path. Backs the picker's diff preview mode; unlike
+    /// `highlight_cache` it's not invalidated on disk changes, since the
+    /// point is to compare against what's currently *applied*, not current.
+    applied_target_files: HashMap<PathBuf, String>,
+    clipboard: ClipboardProvider,
+    monitor_volume: HashMap<Uuid, VolumeSeries>,
+    pub notifications: NotificationCenter,
+    scripting: Option<ScriptEngine>,
+    /// Per-monitor Lua scripts, loaded lazily on first use and cached by
+    /// path so re-matching a monitor doesn't reparse its script. Falls back
+    /// to `scripting` for monitors without their own `script` override.
+    monitor_scripts: HashMap<PathBuf, ScriptEngine>,
+    keymap: Keymap,
+    /// Typed monitor-lifecycle/stream-connection events not yet drained by
+    /// `SessionLogger::flush_new_lifecycle_events`. See [`LifecycleEvent`].
+    pub lifecycle_log: Vec<LifecycleEvent>,
+    /// Originating monitor id for each in-flight/completed analysis feed item,
+    /// keyed by the item's id. `FeedKind::Analysis` only carries the display
+    /// label (which a Lua script's `log_label` may have overridden), so this
+    /// is the only reliable way back to the real `Monitor` once analysis
+    /// completes.
+    analysis_monitor: HashMap<Uuid, Uuid>,
 }
 
 impl App {
-    pub fn new(config: AppConfig, state_path: PathBuf, monitors: Vec<Monitor>) -> Self {
+    pub fn new(
+        config: AppConfig,
+        config_path: PathBuf,
+        store: MonitorStore,
+        feed_store: FeedStore,
+        monitors: Vec<Monitor>,
+    ) -> Self {
         let monitor_activity = monitors
             .iter()
             .map(|monitor| (monitor.id, false))
             .collect::<HashMap<_, _>>();
 
+        let theme = Theme::from_config(&config.theme);
+
+        // Newest-first, matching `push_feed`'s `push_front` ordering.
+        let feed = feed_store
+            .recent(MAX_FEED_ITEMS)
+            .unwrap_or_else(|error| {
+                eprintln!("failed to hydrate feed history: {error}");
+                Vec::new()
+            })
+            .into_iter()
+            .collect::<VecDeque<_>>();
+
+        let monitor_volume = store.load_all_volume().unwrap_or_else(|error| {
+            eprintln!("failed to hydrate monitor volume history: {error}");
+            HashMap::new()
+        });
+
+        let scripting = config.lua_script.as_deref().and_then(|path| {
+            ScriptEngine::load(path)
+                .map_err(|error| eprintln!("failed to load Lua script {}: {error}", path.display()))
+                .ok()
+        });
+
+        let keymap = Keymap::from_config(&config.keymap);
+
         Self {
             should_quit: false,
             focus: FocusPane::Monitors,
             monitors,
             selected_monitor: 0,
-            feed: VecDeque::new(),
+            feed,
             selected_feed: 0,
             add_form: None,
             edit_session: None,
             target_file_picker: None,
+            filter_overlay: None,
+            prompt_picker: None,
             status: "Ready".to_string(),
             provider_names: config.provider_names(),
             stream_connected: false,
             monitor_activity,
             monitor_initiating: HashSet::new(),
-            state_path,
+            store,
+            feed_store,
+            config_path,
             config,
+            theme,
+            applied_target_files: HashMap::new(),
+            clipboard: ClipboardProvider::detect(),
+            monitor_volume,
+            notifications: NotificationCenter::new(),
+            scripting,
+            monitor_scripts: HashMap::new(),
+            keymap,
+            lifecycle_log: Vec::new(),
+            analysis_monitor: HashMap::new(),
         }
     }
 
-    pub fn load_store(path: &PathBuf) -> Result<Vec<Monitor>> {
-        if !path.exists() {
-            return Ok(Vec::new());
-        }
+    /// Queue a typed lifecycle event for `SessionLogger` to pick up on its
+    /// next flush.
+    pub fn log_lifecycle(&mut self, event: LifecycleEvent) {
+        self.lifecycle_log.push(event);
+    }
 
-        let raw = fs::read_to_string(path)
-            .with_context(|| format!("failed to read monitor state at {}", path.display()))?;
-        let store: MonitorStore = serde_json::from_str(&raw)
-            .with_context(|| format!("invalid monitor state at {}", path.display()))?;
-        Ok(store.monitors)
+    /// Open the LMDB-backed monitor store at `dir`, creating it if absent.
+    pub fn open_store(dir: &PathBuf) -> Result<MonitorStore> {
+        MonitorStore::open(dir)
     }
 
+    /// Open the SQLite-backed feed history store at `path`, creating and
+    /// migrating it if absent.
+    pub fn open_feed_store(path: &Path) -> Result<FeedStore> {
+        FeedStore::open(path)
+    }
+
+    /// Persist the in-memory monitor list to the store in one transaction.
     pub fn save_store(&self) -> Result<()> {
-        let state = MonitorStore {
-            monitors: self.monitors.clone(),
-        };
+        self.store.replace_all(&self.monitors)
+    }
 
-        let body = to_string_pretty(&state).context("failed to serialize monitor state")?;
-        fs::write(&self.state_path, body)
-            .with_context(|| format!("failed to write {}", self.state_path.display()))?;
-        Ok(())
+    /// A cloned handle to the monitor store, for handing to a spawned task
+    /// (e.g. `rss::poll_loop`) that needs to persist its own state.
+    pub fn store_handle(&self) -> MonitorStore {
+        self.store.clone()
     }
 
+    /// Push a feed item onto the front of the in-memory feed and upsert it
+    /// into the feed history store. Persistence failures are logged but never
+    /// drop the item from the in-memory feed.
     pub fn push_feed(&mut self, item: FeedItem) {
+        if let Err(error) = self.feed_store.upsert(&item) {
+            eprintln!("failed to persist feed item: {error}");
+        }
+
         self.feed.push_front(item);
         while self.feed.len() > MAX_FEED_ITEMS {
-            self.feed.pop_back();
+            if let Some(evicted) = self.feed.pop_back() {
+                self.analysis_monitor.remove(&evicted.id);
+            }
         }
 
         if self.selected_feed >= self.feed.len() {
@@ -321,6 +552,9 @@ impl App {
     pub fn clear_feed(&mut self) {
         self.feed.clear();
         self.selected_feed = 0;
+        if let Err(error) = self.feed_store.clear() {
+            eprintln!("failed to clear feed history: {error}");
+        }
     }
 
     pub fn push_info(&mut self, message: impl Into<String>) {
@@ -331,6 +565,7 @@ impl App {
             at: Local::now(),
             kind: FeedKind::Info(message),
             url: None,
+            seen: false,
         });
     }
 
@@ -342,10 +577,13 @@ impl App {
             at: Local::now(),
             kind: FeedKind::Error(message),
             url: None,
+            seen: false,
         });
     }
 
-    pub fn push_post(&mut self, post: &StreamPost, monitors: Vec<String>) {
+    /// `display_text` is the post text to show in the feed, which may differ
+    /// from `post.text` if a Lua script overrode it (see `scripting.rs`).
+    pub fn push_post(&mut self, post: &StreamPost, monitors: Vec<String>, display_text: &str) {
         let author = post
             .author_username
             .clone()
@@ -357,38 +595,126 @@ impl App {
             at: Local::now(),
             kind: FeedKind::Post {
                 author,
-                text: post.text.clone(),
+                text: display_text.to_string(),
                 monitors,
             },
             url: Some(post.post_url()),
+            seen: false,
         });
     }
 
-    pub fn push_analysis(
+    /// Record a matched post against `monitor_id`'s volume series and
+    /// persist it, so the sparkline and its history survive a restart.
+    pub fn record_monitor_post(&mut self, monitor_id: Uuid) {
+        let now = Utc::now();
+        let series = self
+            .monitor_volume
+            .entry(monitor_id)
+            .or_insert_with(|| VolumeSeries::new(now));
+        series.record(now);
+        if let Err(error) = self.store.save_volume(monitor_id, series) {
+            eprintln!("failed to persist monitor volume for {monitor_id}: {error}");
+        }
+    }
+
+    /// Advance every tracked monitor's volume ring to the current time,
+    /// zeroing out buckets that have gone stale since the last post. Doesn't
+    /// persist — a pure read shouldn't cost a store round-trip.
+    pub fn tick_monitor_volumes(&mut self) {
+        let now = Utc::now();
+        for series in self.monitor_volume.values_mut() {
+            series.advance(now);
+        }
+    }
+
+    /// Ordered bucket counts for `monitor_id`'s volume series, oldest first.
+    /// A monitor with no recorded activity yet gets an all-zero series of
+    /// the same length.
+    pub fn monitor_volume(&self, monitor_id: Uuid) -> &[u16] {
+        self.monitor_volume
+            .get(&monitor_id)
+            .map(VolumeSeries::counts)
+            .unwrap_or(&EMPTY_VOLUME)
+    }
+
+    /// Drop a streaming analysis placeholder that never produced output (e.g.
+    /// when the request failed before any token arrived).
+    pub fn discard_analysis(&mut self, item_id: Uuid) {
+        if let Some(position) = self.feed.iter().position(|item| item.id == item_id) {
+            self.feed.remove(position);
+            if self.selected_feed >= self.feed.len() {
+                self.selected_feed = self.feed.len().saturating_sub(1);
+            }
+        }
+        if let Err(error) = self.feed_store.remove(item_id) {
+            eprintln!("failed to remove discarded analysis from feed history: {error}");
+        }
+        self.analysis_monitor.remove(&item_id);
+    }
+
+    /// Create an empty streaming analysis item at the front of the feed and
+    /// return its id so later deltas can be appended in place. `monitor_id`
+    /// is tracked separately from the display `monitor` label so the
+    /// originating `Monitor` can still be found by
+    /// [`Self::analysis_monitor_id`] even if a Lua script's `log_label`
+    /// overrides the label to something that isn't a real monitor's.
+    pub fn begin_analysis(
         &mut self,
+        monitor_id: Uuid,
         monitor: String,
         provider: String,
         model: String,
-        output: String,
         url: Option<String>,
-    ) {
+    ) -> Uuid {
+        let id = Uuid::new_v4();
         self.push_feed(FeedItem {
-            id: Uuid::new_v4(),
+            id,
             at: Local::now(),
             kind: FeedKind::Analysis {
                 monitor,
                 provider,
                 model,
-                output,
+                output: String::new(),
             },
             url,
+            seen: false,
         });
+        self.analysis_monitor.insert(id, monitor_id);
+        id
+    }
+
+    /// The monitor that kicked off the analysis feed item `item_id`, as
+    /// tracked by [`Self::begin_analysis`]. Resolving through this instead of
+    /// [`Self::monitor_by_label`] keeps working when a Lua script's
+    /// `log_label` has overridden the item's display label.
+    pub fn analysis_monitor_id(&self, item_id: Uuid) -> Option<Uuid> {
+        self.analysis_monitor.get(&item_id).copied()
+    }
+
+    /// Append a streamed token fragment to an existing analysis item.
+    pub fn append_analysis_delta(&mut self, item_id: Uuid, delta: &str) {
+        if let Some(item) = self.feed.iter_mut().find(|item| item.id == item_id) {
+            if let FeedKind::Analysis { output, .. } = &mut item.kind {
+                output.push_str(delta);
+            }
+            if let Err(error) = self.feed_store.upsert(item) {
+                eprintln!("failed to persist streamed analysis delta: {error}");
+            }
+        }
     }
 
     pub fn monitor_by_tag(&self, tag: &str) -> Option<&Monitor> {
         self.monitors.iter().find(|monitor| monitor.rule_tag == tag)
     }
 
+    pub fn monitor_by_label(&self, label: &str) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.label == label)
+    }
+
+    pub fn monitor_by_id(&self, id: Uuid) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.id == id)
+    }
+
     pub fn toggle_focus(&mut self) {
         self.focus = match self.focus {
             FocusPane::Monitors => FocusPane::Feed,
@@ -409,6 +735,7 @@ impl App {
             FocusPane::Feed => {
                 if !self.feed.is_empty() {
                     self.selected_feed = self.selected_feed.saturating_sub(1);
+                    self.mark_selected_feed_seen();
                 }
             }
         }
@@ -426,6 +753,7 @@ impl App {
                 if !self.feed.is_empty() {
                     self.selected_feed =
                         (self.selected_feed + 1).min(self.feed.len().saturating_sub(1));
+                    self.mark_selected_feed_seen();
                 }
             }
         }
@@ -439,6 +767,8 @@ impl App {
         ));
         self.edit_session = None;
         self.target_file_picker = None;
+        self.filter_overlay = None;
+        self.prompt_picker = None;
     }
 
     pub fn open_edit_form(&mut self, monitor: Monitor) {
@@ -451,12 +781,15 @@ impl App {
             original_monitor: monitor,
         });
         self.target_file_picker = None;
+        self.filter_overlay = None;
+        self.prompt_picker = None;
     }
 
     pub fn close_add_form(&mut self) {
         self.add_form = None;
         self.edit_session = None;
         self.target_file_picker = None;
+        self.prompt_picker = None;
     }
 
     pub fn open_target_file_picker(&mut self) -> Result<usize> {
@@ -467,7 +800,10 @@ impl App {
             directory,
             entries,
             selected: 0,
+            highlight_cache: HashMap::new(),
+            preview_mode: PreviewMode::default(),
         });
+        self.prompt_picker = None;
         Ok(count)
     }
 
@@ -475,6 +811,49 @@ impl App {
         self.target_file_picker = None;
     }
 
+    /// Re-read `directory` and replace the open picker's `entries` in
+    /// place, keeping the same file selected where it still exists. A no-op
+    /// if the picker isn't open. Returns the new entry count on success.
+    pub fn refresh_target_file_picker(&mut self) -> Option<Result<usize>> {
+        let picker = self.target_file_picker.as_mut()?;
+        let selected_name = picker.entries.get(picker.selected).map(|entry| entry.file_name.clone());
+
+        Some(match load_target_file_entries(&picker.directory) {
+            Ok(entries) => {
+                let count = entries.len();
+                picker.entries = entries;
+                picker.selected = selected_name
+                    .and_then(|name| picker.entries.iter().position(|entry| entry.file_name == name))
+                    .unwrap_or(0)
+                    .min(picker.entries.len().saturating_sub(1));
+                picker.highlight_cache.clear();
+                Ok(count)
+            }
+            Err(error) => Err(error),
+        })
+    }
+
+    /// Advance the open picker's preview mode to the next one in the cycle.
+    /// A no-op if the picker isn't open.
+    pub fn cycle_preview_mode(&mut self) {
+        if let Some(picker) = self.target_file_picker.as_mut() {
+            picker.preview_mode = picker.preview_mode.next();
+        }
+    }
+
+    /// Record `raw` as the content last successfully applied from `path`,
+    /// for the diff preview mode to compare future edits against.
+    pub fn record_applied_target_file(&mut self, path: PathBuf, raw: String) {
+        self.applied_target_files.insert(path, raw);
+    }
+
+    /// The content last successfully applied from `path`, if any.
+    pub fn applied_target_file_raw(&self, path: &Path) -> Option<&str> {
+        self.applied_target_files
+            .get(path)
+            .map(|raw| raw.as_str())
+    }
+
     pub fn move_target_file_selection(&mut self, delta: i32) {
         let Some(picker) = self.target_file_picker.as_mut() else {
             return;
@@ -489,11 +868,258 @@ impl App {
         picker.selected = next as usize;
     }
 
+    /// Syntax-highlighted lines for the currently selected target file, if
+    /// it parsed successfully and highlighting is available. Tokenizes once
+    /// per (path, mtime) and reuses the result on subsequent frames.
+    pub fn target_file_preview_highlight(&mut self) -> Option<&[Line<'static>]> {
+        let theme = self.theme.clone();
+        let picker = self.target_file_picker.as_mut()?;
+        let entry = picker.entries.get(picker.selected)?;
+        if entry.parsed.is_err() {
+            return None;
+        }
+
+        let up_to_date = picker
+            .highlight_cache
+            .get(&entry.path)
+            .is_some_and(|(mtime, _)| *mtime == entry.mtime);
+
+        if !up_to_date {
+            let highlighted = yaml_syntax::highlight_yaml(&theme, &entry.raw)?;
+            picker
+                .highlight_cache
+                .insert(entry.path.clone(), (entry.mtime, highlighted));
+        }
+
+        picker
+            .highlight_cache
+            .get(&entry.path)
+            .map(|(_, lines)| lines.as_slice())
+    }
+
     pub fn selected_target_file_entry(&self) -> Option<&TargetFileEntry> {
         let picker = self.target_file_picker.as_ref()?;
         picker.entries.get(picker.selected)
     }
 
+    /// Open the prompt-library picker, browsing the prompts saved in config.
+    pub fn open_prompt_picker(&mut self) {
+        self.target_file_picker = None;
+        self.prompt_picker = Some(PromptPicker {
+            prompts: self.config.prompt_library.clone(),
+            selected: 0,
+            save_name_input: None,
+        });
+    }
+
+    pub fn close_prompt_picker(&mut self) {
+        self.prompt_picker = None;
+    }
+
+    pub fn move_prompt_picker_selection(&mut self, delta: i32) {
+        let Some(picker) = self.prompt_picker.as_mut() else {
+            return;
+        };
+        if picker.prompts.is_empty() {
+            picker.selected = 0;
+            return;
+        }
+
+        let len = picker.prompts.len() as i32;
+        let next = (picker.selected as i32 + delta).rem_euclid(len);
+        picker.selected = next as usize;
+    }
+
+    pub fn selected_prompt_entry(&self) -> Option<&PromptTemplate> {
+        let picker = self.prompt_picker.as_ref()?;
+        picker.prompts.get(picker.selected)
+    }
+
+    /// Enter the "save current prompt as..." sub-mode, seeding an empty name.
+    pub fn prompt_picker_begin_save(&mut self) {
+        if let Some(picker) = self.prompt_picker.as_mut() {
+            picker.save_name_input = Some(String::new());
+        }
+    }
+
+    pub fn prompt_picker_cancel_save(&mut self) {
+        if let Some(picker) = self.prompt_picker.as_mut() {
+            picker.save_name_input = None;
+        }
+    }
+
+    pub fn prompt_picker_push_char(&mut self, ch: char) {
+        if let Some(picker) = self.prompt_picker.as_mut() {
+            if let Some(name) = picker.save_name_input.as_mut() {
+                name.push(ch);
+            }
+        }
+    }
+
+    pub fn prompt_picker_pop_char(&mut self) {
+        if let Some(picker) = self.prompt_picker.as_mut() {
+            if let Some(name) = picker.save_name_input.as_mut() {
+                name.pop();
+            }
+        }
+    }
+
+    /// Promote `prompt_text` into the config's prompt library under the name
+    /// currently being typed, then persist config to disk and refresh the
+    /// picker's list so the new entry shows up immediately.
+    pub fn prompt_picker_commit_save(&mut self, prompt_text: String) -> Result<()> {
+        let Some(picker) = self.prompt_picker.as_ref() else {
+            anyhow::bail!("prompt picker is not open");
+        };
+        let name = picker
+            .save_name_input
+            .as_deref()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            anyhow::bail!("prompt name cannot be empty");
+        }
+
+        self.config.upsert_prompt(name, prompt_text);
+        self.config.save(&self.config_path)?;
+
+        if let Some(picker) = self.prompt_picker.as_mut() {
+            picker.prompts = self.config.prompt_library.clone();
+            picker.save_name_input = None;
+        }
+
+        Ok(())
+    }
+
+    /// Open the fuzzy-filter overlay over whichever pane currently has focus.
+    pub fn open_filter_overlay(&mut self) {
+        self.add_form = None;
+        self.edit_session = None;
+        self.target_file_picker = None;
+        self.prompt_picker = None;
+        self.filter_overlay = Some(FilterOverlay {
+            pane: self.focus,
+            query: String::new(),
+            selected: 0,
+            cache: None,
+        });
+    }
+
+    pub fn close_filter_overlay(&mut self) {
+        self.filter_overlay = None;
+    }
+
+    pub fn filter_overlay_push_char(&mut self, ch: char) {
+        if let Some(overlay) = self.filter_overlay.as_mut() {
+            overlay.query.push(ch);
+            overlay.selected = 0;
+        }
+    }
+
+    pub fn filter_overlay_pop_char(&mut self) {
+        if let Some(overlay) = self.filter_overlay.as_mut() {
+            overlay.query.pop();
+            overlay.selected = 0;
+        }
+    }
+
+    /// The label/value text a fuzzy query is matched against for a given pane
+    /// entry; shared by match scoring and overlay rendering so highlighted
+    /// ranges always line up with what's shown.
+    pub fn filter_candidate_text(&self, pane: FocusPane, index: usize) -> Option<String> {
+        match pane {
+            FocusPane::Monitors => self.monitors.get(index).map(|monitor| {
+                format!(
+                    "{} {} {}",
+                    monitor.label,
+                    monitor.input_value,
+                    monitor.kind.display()
+                )
+            }),
+            FocusPane::Feed => self.feed.get(index).map(|item| item.summary()),
+        }
+    }
+
+    fn compute_filter_matches(&self, pane: FocusPane, query: &str) -> Vec<(usize, FuzzyMatch)> {
+        let len = match pane {
+            FocusPane::Monitors => self.monitors.len(),
+            FocusPane::Feed => self.feed.len(),
+        };
+
+        let mut matches = (0..len)
+            .filter_map(|index| {
+                let candidate = self.filter_candidate_text(pane, index)?;
+                fuzzy::fuzzy_match(query, &candidate).map(|fuzzy_match| (index, fuzzy_match))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+        matches
+    }
+
+    fn ensure_filter_overlay_matches(&mut self) {
+        let Some(overlay) = self.filter_overlay.as_ref() else {
+            return;
+        };
+        let up_to_date = overlay
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.query == overlay.query);
+        if up_to_date {
+            return;
+        }
+
+        let matches = self.compute_filter_matches(overlay.pane, &overlay.query);
+        if let Some(overlay) = self.filter_overlay.as_mut() {
+            overlay.selected = overlay.selected.min(matches.len().saturating_sub(1));
+            overlay.cache = Some(FilterCache {
+                query: overlay.query.clone(),
+                matches,
+            });
+        }
+    }
+
+    /// Matches for the current overlay query, sorted by descending score
+    /// (stable on ties). Recomputed only when the query has changed since the
+    /// last call.
+    pub fn filter_overlay_matches(&mut self) -> &[(usize, FuzzyMatch)] {
+        self.ensure_filter_overlay_matches();
+        self.filter_overlay
+            .as_ref()
+            .and_then(|overlay| overlay.cache.as_ref())
+            .map(|cache| cache.matches.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn move_filter_overlay_selection(&mut self, delta: i32) {
+        self.ensure_filter_overlay_matches();
+        let Some(overlay) = self.filter_overlay.as_mut() else {
+            return;
+        };
+        let len = overlay
+            .cache
+            .as_ref()
+            .map(|cache| cache.matches.len())
+            .unwrap_or(0);
+        if len == 0 {
+            overlay.selected = 0;
+            return;
+        }
+
+        let next = (overlay.selected as i32 + delta).rem_euclid(len as i32);
+        overlay.selected = next as usize;
+    }
+
+    /// The underlying pane index and match currently selected in the overlay.
+    pub fn selected_filter_overlay_entry(&self) -> Option<(usize, &FuzzyMatch)> {
+        let overlay = self.filter_overlay.as_ref()?;
+        let cache = overlay.cache.as_ref()?;
+        cache
+            .matches
+            .get(overlay.selected)
+            .map(|(index, fuzzy_match)| (*index, fuzzy_match))
+    }
+
     pub fn selected_monitor(&self) -> Option<&Monitor> {
         self.monitors.get(self.selected_monitor)
     }
@@ -502,9 +1128,69 @@ impl App {
         self.feed.get(self.selected_feed)
     }
 
+    pub fn clipboard(&self) -> &ClipboardProvider {
+        &self.clipboard
+    }
+
+    pub fn scripting(&self) -> Option<&ScriptEngine> {
+        self.scripting.as_ref()
+    }
+
+    /// Resolves the Lua engine to run `monitor`'s matches through: its own
+    /// `script` override if set (loaded and cached on first use), otherwise
+    /// the global `scripting` engine.
+    pub fn script_for(&mut self, monitor: &Monitor) -> Option<&ScriptEngine> {
+        let Some(path) = &monitor.script else {
+            return self.scripting.as_ref();
+        };
+
+        if !self.monitor_scripts.contains_key(path) {
+            match ScriptEngine::load(path) {
+                Ok(engine) => {
+                    self.monitor_scripts.insert(path.clone(), engine);
+                }
+                Err(error) => {
+                    self.push_error(format!(
+                        "failed to load Lua script {}: {error}",
+                        path.display()
+                    ));
+                    return self.scripting.as_ref();
+                }
+            }
+        }
+        self.monitor_scripts.get(path)
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Mark the item at `selected_feed` as seen, e.g. after scrolling onto it.
+    pub fn mark_selected_feed_seen(&mut self) {
+        if let Some(item) = self.feed.get_mut(self.selected_feed) {
+            item.seen = true;
+        }
+    }
+
+    /// Clear the unseen highlight from every item in the feed.
+    pub fn mark_all_feed_seen(&mut self) {
+        for item in self.feed.iter_mut() {
+            item.seen = true;
+        }
+    }
+
+    /// Count of post/analysis items the user hasn't scrolled onto yet.
+    pub fn unseen_feed_count(&self) -> usize {
+        self.feed
+            .iter()
+            .filter(|item| item.tracks_seen() && !item.seen)
+            .count()
+    }
+
     pub fn add_monitor(&mut self, monitor: Monitor) {
         self.monitor_activity
             .insert(monitor.id, monitor.enabled && self.stream_connected);
+        self.log_lifecycle(LifecycleEvent::MonitorAdded(monitor.label.clone()));
         self.monitors.push(monitor);
         self.selected_monitor = self.monitors.len().saturating_sub(1);
     }
@@ -531,6 +1217,14 @@ impl App {
         let removed = self.monitors.remove(position);
         self.monitor_activity.remove(&removed.id);
         self.monitor_initiating.remove(&removed.id);
+        self.monitor_volume.remove(&removed.id);
+        if let Err(error) = self.store.remove_volume(removed.id) {
+            eprintln!("failed to remove monitor volume for {}: {error}", removed.id);
+        }
+        if let Err(error) = self.store.remove_rss_seen(removed.id) {
+            eprintln!("failed to remove RSS seen-set for {}: {error}", removed.id);
+        }
+        self.log_lifecycle(LifecycleEvent::MonitorDeleted(removed.label.clone()));
 
         if self.selected_monitor >= self.monitors.len() && !self.monitors.is_empty() {
             self.selected_monitor = self.monitors.len() - 1;
@@ -549,6 +1243,13 @@ impl App {
     }
 
     pub fn set_stream_connected(&mut self, connected: bool) {
+        if connected != self.stream_connected {
+            self.log_lifecycle(if connected {
+                LifecycleEvent::StreamConnected
+            } else {
+                LifecycleEvent::StreamDisconnected
+            });
+        }
         self.stream_connected = connected;
         self.set_all_monitors_active(connected);
         if connected {
@@ -616,37 +1317,42 @@ impl App {
     }
 
     pub fn activate_monitor_with_rule(&mut self, monitor_id: Uuid, new_rule_id: String) -> bool {
-        if let Some(monitor) = self
+        let Some(monitor) = self
             .monitors
             .iter_mut()
             .find(|monitor| monitor.id == monitor_id)
-        {
-            monitor.rule_id = new_rule_id;
-            monitor.enabled = true;
-            self.monitor_activity
-                .insert(monitor_id, self.stream_connected);
-            if self.stream_connected {
-                self.monitor_initiating.remove(&monitor_id);
-            }
-            return true;
-        }
+        else {
+            return false;
+        };
 
-        false
+        monitor.rule_id = new_rule_id;
+        monitor.enabled = true;
+        let label = monitor.label.clone();
+        self.monitor_activity
+            .insert(monitor_id, self.stream_connected);
+        if self.stream_connected {
+            self.monitor_initiating.remove(&monitor_id);
+        }
+        self.log_lifecycle(LifecycleEvent::MonitorActivated(label));
+        true
     }
 
     pub fn deactivate_monitor(&mut self, monitor_id: Uuid) -> bool {
-        if let Some(monitor) = self
+        let Some(monitor) = self
             .monitors
             .iter_mut()
             .find(|monitor| monitor.id == monitor_id)
-        {
-            monitor.enabled = false;
-            monitor.rule_id.clear();
-            self.monitor_activity.insert(monitor_id, false);
-            self.monitor_initiating.remove(&monitor_id);
-            return true;
-        }
-        false
+        else {
+            return false;
+        };
+
+        monitor.enabled = false;
+        monitor.rule_id.clear();
+        let label = monitor.label.clone();
+        self.monitor_activity.insert(monitor_id, false);
+        self.monitor_initiating.remove(&monitor_id);
+        self.log_lifecycle(LifecycleEvent::MonitorDeactivated(label));
+        true
     }
 
     pub fn disable_monitor_preserve_rule(&mut self, monitor_id: Uuid) -> bool {
@@ -0,0 +1,110 @@
+//! Clipboard abstraction, modeled after helix's `ClipboardProvider`: detect
+//! the platform backend once at startup, then copy through whichever one is
+//! available. Shells out to the platform tool rather than linking a
+//! clipboard crate, the same way [`crate::secrets::CommandSource`] shells out
+//! for secrets — one less thing that has to work on every platform to build.
+
+use std::{env, process::Stdio};
+
+use anyhow::{Context, Result, bail};
+use base64::Engine as _;
+
+/// Which backend [`ClipboardProvider::detect`] picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    WlCopy,
+    Xclip,
+    Pbcopy,
+    /// OSC-52 terminal escape sequence: works over SSH with no clipboard
+    /// binary on `$PATH`, as long as the terminal emulator honors it.
+    Osc52,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClipboardProvider {
+    backend: Backend,
+}
+
+impl ClipboardProvider {
+    /// Probe the environment once at startup for a working clipboard
+    /// backend, preferring a native one and falling back to OSC-52 when
+    /// none is reachable.
+    pub fn detect() -> Self {
+        let backend = if env::var_os("WAYLAND_DISPLAY").is_some() && command_exists("wl-copy") {
+            Backend::WlCopy
+        } else if env::var_os("DISPLAY").is_some() && command_exists("xclip") {
+            Backend::Xclip
+        } else if command_exists("pbcopy") {
+            Backend::Pbcopy
+        } else {
+            Backend::Osc52
+        };
+        Self { backend }
+    }
+
+    /// The backend name, for status messages.
+    pub fn backend_name(&self) -> &'static str {
+        match self.backend {
+            Backend::WlCopy => "wl-copy",
+            Backend::Xclip => "xclip",
+            Backend::Pbcopy => "pbcopy",
+            Backend::Osc52 => "OSC-52",
+        }
+    }
+
+    /// Copy `text` to the system clipboard through the detected backend.
+    pub fn copy(&self, text: &str) -> Result<()> {
+        match self.backend {
+            Backend::WlCopy => pipe_to_command("wl-copy", &[], text),
+            Backend::Xclip => pipe_to_command("xclip", &["-selection", "clipboard"], text),
+            Backend::Pbcopy => pipe_to_command("pbcopy", &[], text),
+            Backend::Osc52 => copy_osc52(text),
+        }
+    }
+}
+
+/// Whether `name` resolves to a file somewhere on `$PATH`.
+fn command_exists(name: &str) -> bool {
+    let Some(path_var) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&path_var).any(|dir| dir.join(name).is_file())
+}
+
+fn pipe_to_command(program: &str, args: &[&str], text: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to spawn `{program}`"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .with_context(|| format!("failed to write to `{program}` stdin"))?;
+    }
+
+    let status = child
+        .wait()
+        .with_context(|| format!("`{program}` could not be waited on"))?;
+    if !status.success() {
+        bail!("`{program}` exited with {status}");
+    }
+    Ok(())
+}
+
+/// Write the OSC-52 set-clipboard escape sequence directly to stdout. Most
+/// terminal emulators apply it immediately and don't echo it back.
+fn copy_osc52(text: &str) -> Result<()> {
+    use std::io::Write;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text.as_bytes());
+    print!("\x1b]52;c;{encoded}\x07");
+    std::io::stdout()
+        .flush()
+        .context("failed to flush OSC-52 clipboard sequence")
+}
@@ -1,8 +1,24 @@
-use std::{env, fs, path::PathBuf};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::theme::ThemeConfig;
+
+/// Wire protocol a provider speaks. Most OpenAI-compatible endpoints use
+/// [`ProviderProtocol::OpenAiChat`]; native Anthropic endpoints use a different
+/// request body and auth scheme and need [`ProviderProtocol::Anthropic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderProtocol {
+    #[default]
+    OpenAiChat,
+    Anthropic,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiProvider {
     pub name: String,
@@ -10,6 +26,26 @@ pub struct AiProvider {
     pub model: String,
     pub api_key: Option<String>,
     pub api_key_env: Option<String>,
+    /// Wire protocol spoken by this provider. Defaults to the OpenAI chat schema.
+    #[serde(default)]
+    pub protocol: ProviderProtocol,
+    /// Optional per-provider proxy URL (`http://`, `https://`, or `socks5://`).
+    /// Falls back to `HTTPS_PROXY`/`ALL_PROXY` when unset.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Per-provider request timeout in seconds. Defaults to 60s when unset.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Sampling temperature. When unset, OpenAI-compatible providers fall back
+    /// to `0.2`; Anthropic requests omit it and use the API's own default.
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Maximum tokens to generate. Required by some providers (e.g. Anthropic).
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Nucleus-sampling cutoff, sent only when set.
+    #[serde(default)]
+    pub top_p: Option<f32>,
 }
 
 impl AiProvider {
@@ -27,12 +63,42 @@ impl AiProvider {
     }
 }
 
+/// A named, reusable AI analysis prompt, selectable from the add/edit modal's
+/// prompt-library picker instead of retyping the same instructions per monitor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub text: String,
+}
+
+/// Shell commands run when the feed gains a new item, letting people wire
+/// matched posts and analyses into notify scripts, webhooks, or `jq`
+/// pipelines without touching the crate. Context is handed to the spawned
+/// process both as `XMON_*` environment variables and as JSON on stdin, the
+/// way xplr hands context to its spawned hooks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Run for every post matched by an enabled monitor.
+    pub on_post: Option<String>,
+    /// Run when a streamed or blocking AI analysis finishes.
+    pub on_analysis: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ResolvedAiProvider {
     pub name: String,
     pub base_url: String,
     pub model: String,
     pub api_key: String,
+    pub protocol: ProviderProtocol,
+    pub proxy: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub top_p: Option<f32>,
+    pub retries: u32,
+    pub retry_base_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +113,129 @@ pub struct AppConfig {
     pub default_ai_provider: String,
     #[serde(default = "default_ai_providers")]
     pub ai_providers: Vec<AiProvider>,
+    /// How many times to retry a transient AI failure (429 / 5xx) before giving up.
+    #[serde(default = "default_ai_retries")]
+    pub ai_retries: u32,
+    /// Base backoff delay in milliseconds; doubled on each successive retry.
+    #[serde(default = "default_ai_retry_base_ms")]
+    pub ai_retry_base_ms: u64,
+    /// Terminal theme: a named palette plus optional per-role style overrides.
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    /// Named analysis prompts shared across monitors, browsable from the
+    /// add/edit modal's prompt-library picker.
+    #[serde(default = "default_prompt_library")]
+    pub prompt_library: Vec<PromptTemplate>,
+    /// External command hooks fired on new posts and completed analyses.
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// Path to a Lua script exposing `on_post(post)` for custom match
+    /// filtering and per-post AI routing overrides.
+    #[serde(default)]
+    pub lua_script: Option<PathBuf>,
+    /// Base delay, in seconds, before the first stream reconnect attempt.
+    /// Doubled on each consecutive failure up to `stream_reconnect_cap_secs`.
+    #[serde(default = "default_stream_reconnect_base_secs")]
+    pub stream_reconnect_base_secs: u64,
+    /// Upper bound, in seconds, on the exponential reconnect backoff.
+    #[serde(default = "default_stream_reconnect_cap_secs")]
+    pub stream_reconnect_cap_secs: u64,
+    /// Stop attempting to reconnect after this many consecutive failures.
+    /// `0` means retry forever.
+    #[serde(default)]
+    pub stream_reconnect_max_attempts: u32,
+    /// Outbound webhook sinks that matched posts and completed analyses are
+    /// forwarded to, e.g. Slack/Discord/generic JSON endpoints.
+    #[serde(default)]
+    pub forwarders: Vec<ForwarderConfig>,
+    /// Keybinding overrides for the main feed/monitors view, applied on top
+    /// of [`crate::keymap::Keymap`]'s built-in defaults.
+    #[serde(default)]
+    pub keymap: Vec<KeyBinding>,
+    /// How often, in seconds, an RSS/Atom target is re-fetched by
+    /// `rss::poll_loop`.
+    #[serde(default = "default_rss_poll_interval_secs")]
+    pub rss_poll_interval_secs: u64,
+    /// Redis Stream to `XADD` every matched post to, for durability and
+    /// external consumers. Unset disables the publisher entirely. See
+    /// `redis_stream::spawn`.
+    #[serde(default)]
+    pub redis: Option<RedisConfig>,
+}
+
+/// Connection details for [`crate::redis_stream`]'s `XADD` publisher task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisConfig {
+    pub url: String,
+    #[serde(default = "default_redis_stream_key")]
+    pub stream_key: String,
+}
+
+fn default_redis_stream_key() -> String {
+    "x-monitor:posts".to_string()
+}
+
+/// A remappable action from the main feed/monitors view. See
+/// [`crate::keymap::Keymap`] for how these resolve from key events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    ToggleFocus,
+    AddMonitor,
+    EditMonitor,
+    ToggleActivation,
+    Delete,
+    Reconnect,
+    TerminateAll,
+    OpenUrl,
+    ClearFeed,
+    MoveUp,
+    MoveDown,
+}
+
+/// One `action -> key` override. `key` is a spec string like `"q"`, `"tab"`,
+/// or `"ctrl+r"`, parsed by [`crate::keymap::parse_key_spec`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub action: Action,
+    pub key: String,
+}
+
+/// Which events a [`ForwarderConfig`] wants delivered to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwarderEvent {
+    Post,
+    Analysis,
+}
+
+/// One outbound webhook sink: a URL, an optional bearer/auth header value,
+/// and which event types get POSTed to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForwarderConfig {
+    pub url: String,
+    #[serde(default)]
+    pub auth_header: Option<String>,
+    #[serde(default = "default_forwarder_events")]
+    pub events: Vec<ForwarderEvent>,
+    /// Payload shape to POST. See [`crate::forwarders::NotificationSink`].
+    #[serde(default)]
+    pub format: ForwarderFormat,
+}
+
+/// Which JSON shape a [`ForwarderConfig`] POSTs. `Discord` wraps the event in
+/// a Discord webhook embed instead of the plain generic payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwarderFormat {
+    #[default]
+    Generic,
+    Discord,
+}
+
+fn default_forwarder_events() -> Vec<ForwarderEvent> {
+    vec![ForwarderEvent::Post, ForwarderEvent::Analysis]
 }
 
 impl Default for AppConfig {
@@ -57,6 +246,19 @@ impl Default for AppConfig {
             monitor_config_dir: default_monitor_config_dir(),
             default_ai_provider: default_ai_provider_name(),
             ai_providers: default_ai_providers(),
+            ai_retries: default_ai_retries(),
+            ai_retry_base_ms: default_ai_retry_base_ms(),
+            theme: ThemeConfig::default(),
+            prompt_library: default_prompt_library(),
+            hooks: HooksConfig::default(),
+            lua_script: None,
+            stream_reconnect_base_secs: default_stream_reconnect_base_secs(),
+            stream_reconnect_cap_secs: default_stream_reconnect_cap_secs(),
+            stream_reconnect_max_attempts: 0,
+            forwarders: Vec::new(),
+            keymap: Vec::new(),
+            rss_poll_interval_secs: default_rss_poll_interval_secs(),
+            redis: None,
         }
     }
 }
@@ -115,9 +317,32 @@ impl AppConfig {
                 .unwrap_or_else(default_ai_provider_name);
         }
 
+        if config.prompt_library.is_empty() {
+            config.prompt_library = default_prompt_library();
+        }
+
         Ok((config, config_path, created_default))
     }
 
+    /// Write the config back to `path`, e.g. after the prompt library changes.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let rendered = toml::to_string_pretty(self).context("failed to serialize config")?;
+        fs::write(path, rendered).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Insert or overwrite a prompt template, keyed by name (case-insensitive).
+    pub fn upsert_prompt(&mut self, name: String, text: String) {
+        if let Some(existing) = self
+            .prompt_library
+            .iter_mut()
+            .find(|prompt| prompt.name.eq_ignore_ascii_case(&name))
+        {
+            existing.text = text;
+        } else {
+            self.prompt_library.push(PromptTemplate { name, text });
+        }
+    }
+
     pub fn provider_names(&self) -> Vec<String> {
         self.ai_providers
             .iter()
@@ -140,6 +365,14 @@ impl AppConfig {
                     base_url: provider.base_url.clone(),
                     model: provider.model.clone(),
                     api_key,
+                    protocol: provider.protocol,
+                    proxy: provider.proxy.clone(),
+                    timeout_secs: provider.timeout_secs,
+                    temperature: provider.temperature,
+                    max_tokens: provider.max_tokens,
+                    top_p: provider.top_p,
+                    retries: self.ai_retries,
+                    retry_base_ms: self.ai_retry_base_ms,
                 })
         })
     }
@@ -155,7 +388,9 @@ fn first_non_empty_env(keys: &[&str]) -> Option<String> {
 }
 
 fn default_state_path() -> PathBuf {
-    PathBuf::from("x-monitor-state.json")
+    // Directory holding the LMDB monitor store's data/lock files, not a
+    // single file.
+    PathBuf::from("x-monitor-state")
 }
 
 fn default_monitor_config_dir() -> PathBuf {
@@ -166,6 +401,46 @@ fn default_ai_provider_name() -> String {
     "grok".to_string()
 }
 
+fn default_ai_retries() -> u32 {
+    3
+}
+
+fn default_ai_retry_base_ms() -> u64 {
+    500
+}
+
+fn default_stream_reconnect_base_secs() -> u64 {
+    1
+}
+
+fn default_stream_reconnect_cap_secs() -> u64 {
+    60
+}
+
+fn default_rss_poll_interval_secs() -> u64 {
+    300
+}
+
+fn default_prompt_library() -> Vec<PromptTemplate> {
+    vec![
+        PromptTemplate {
+            name: "Sentiment".to_string(),
+            text: "Summarize the overall sentiment of this post (positive, negative, or neutral) and the single strongest reason why."
+                .to_string(),
+        },
+        PromptTemplate {
+            name: "Breaking news detection".to_string(),
+            text: "Determine whether this post describes a genuinely new, time-sensitive event rather than commentary or a repost, and explain what would make it worth an alert."
+                .to_string(),
+        },
+        PromptTemplate {
+            name: "Spam filtering".to_string(),
+            text: "Flag whether this post looks like spam, a scam, or bot-generated engagement bait, and give a one-line justification."
+                .to_string(),
+        },
+    ]
+}
+
 fn default_ai_providers() -> Vec<AiProvider> {
     vec![
         AiProvider {
@@ -174,6 +449,12 @@ fn default_ai_providers() -> Vec<AiProvider> {
             model: "grok-4-1-fast-non-reasoning".to_string(),
             api_key: None,
             api_key_env: Some("XAI_API_KEY".to_string()),
+            protocol: ProviderProtocol::OpenAiChat,
+            proxy: None,
+            timeout_secs: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
         },
         AiProvider {
             name: "openrouter".to_string(),
@@ -181,6 +462,12 @@ fn default_ai_providers() -> Vec<AiProvider> {
             model: "x-ai/grok-4.1-fast".to_string(),
             api_key: None,
             api_key_env: Some("OPENROUTER_API_KEY".to_string()),
+            protocol: ProviderProtocol::OpenAiChat,
+            proxy: None,
+            timeout_secs: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
         },
         AiProvider {
             name: "gemini".to_string(),
@@ -188,6 +475,12 @@ fn default_ai_providers() -> Vec<AiProvider> {
             model: "gemini-3-flash-preview".to_string(),
             api_key: None,
             api_key_env: Some("GEMINI_API_KEY".to_string()),
+            protocol: ProviderProtocol::OpenAiChat,
+            proxy: None,
+            timeout_secs: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
         },
         AiProvider {
             name: "openai".to_string(),
@@ -195,6 +488,25 @@ fn default_ai_providers() -> Vec<AiProvider> {
             model: "gpt-5-nano".to_string(),
             api_key: None,
             api_key_env: Some("OPENAI_API_KEY".to_string()),
+            protocol: ProviderProtocol::OpenAiChat,
+            proxy: None,
+            timeout_secs: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+        },
+        AiProvider {
+            name: "claude".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            model: "claude-haiku-4-5".to_string(),
+            api_key: None,
+            api_key_env: Some("ANTHROPIC_API_KEY".to_string()),
+            protocol: ProviderProtocol::Anthropic,
+            proxy: None,
+            timeout_secs: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
         },
         AiProvider {
             name: "custom".to_string(),
@@ -202,6 +514,12 @@ fn default_ai_providers() -> Vec<AiProvider> {
             model: String::new(),
             api_key: None,
             api_key_env: None,
+            protocol: ProviderProtocol::OpenAiChat,
+            proxy: None,
+            timeout_secs: None,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
         },
     ]
 }
@@ -0,0 +1,58 @@
+//! Minimal line-oriented diff backing the target-file picker's diff preview
+//! mode. No external dependency: a classic LCS table over lines, filled from
+//! the end of both inputs, then walked forwards from the start to emit a
+//! unified add/remove/unchanged sequence. Fine for config-sized
+//! files; not meant for large documents.
+
+/// One line of a diff result, borrowed from whichever side it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Unchanged(&'a str),
+    Added(&'a str),
+    Removed(&'a str),
+}
+
+/// Diff `old` against `new`, line by line.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let (rows, cols) = (old_lines.len(), new_lines.len());
+
+    // lcs[i][j] = length of the longest common subsequence of old[i..], new[j..]
+    let mut lcs = vec![vec![0usize; cols + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(rows + cols);
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        if old_lines[i] == new_lines[j] {
+            result.push(DiffLine::Unchanged(old_lines[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine::Removed(old_lines[i]));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new_lines[j]));
+            j += 1;
+        }
+    }
+    while i < rows {
+        result.push(DiffLine::Removed(old_lines[i]));
+        i += 1;
+    }
+    while j < cols {
+        result.push(DiffLine::Added(new_lines[j]));
+        j += 1;
+    }
+
+    result
+}
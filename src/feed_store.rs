@@ -0,0 +1,275 @@
+//! Embedded SQLite-backed store (via `rusqlite`) for feed history. Unlike
+//! [`crate::store::MonitorStore`], which holds the canonical monitor list,
+//! the feed was previously pure in-memory state: every restart lost all
+//! captured posts, analyses, and errors. This gives it a real home, with a
+//! `schema_version` table so future columns can migrate in place instead of
+//! forcing a wipe.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use uuid::Uuid;
+
+use crate::models::{FeedItem, FeedKind};
+
+const SCHEMA_MIGRATIONS: &[&str] = &[
+    // v1
+    "CREATE TABLE feed_items (
+        id TEXT PRIMARY KEY,
+        at TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        author TEXT,
+        text TEXT,
+        monitors TEXT,
+        monitor TEXT,
+        provider TEXT,
+        model TEXT,
+        output TEXT,
+        message TEXT,
+        url TEXT,
+        seen INTEGER NOT NULL
+    );
+    CREATE INDEX feed_items_at ON feed_items (at);",
+];
+
+pub struct FeedStore {
+    conn: Connection,
+}
+
+impl std::fmt::Debug for FeedStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FeedStore").finish_non_exhaustive()
+    }
+}
+
+impl FeedStore {
+    /// Open (creating and migrating if necessary) the SQLite database at
+    /// `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create feed store directory {}", parent.display()))?;
+        }
+
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open feed store at {}", path.display()))?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .context("failed to enable WAL mode on feed store")?;
+
+        let mut store = Self { conn };
+        store.migrate().context("failed to migrate feed store schema")?;
+        Ok(store)
+    }
+
+    fn migrate(&mut self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)",
+        )?;
+
+        let current: i64 = self
+            .conn
+            .query_row("SELECT version FROM schema_version", [], |row| row.get(0))
+            .optional()?
+            .unwrap_or(0);
+
+        let txn = self.conn.transaction()?;
+        for migration in SCHEMA_MIGRATIONS.iter().skip(current as usize) {
+            txn.execute_batch(migration)?;
+        }
+        txn.execute("DELETE FROM schema_version", [])?;
+        txn.execute(
+            "INSERT INTO schema_version (version) VALUES (?1)",
+            params![SCHEMA_MIGRATIONS.len() as i64],
+        )?;
+        txn.commit()?;
+
+        Ok(())
+    }
+
+    /// Insert a new feed item, or overwrite it in place if `item.id` is
+    /// already present — used both for the initial row and for updating a
+    /// streaming analysis as tokens arrive.
+    pub fn upsert(&self, item: &FeedItem) -> Result<()> {
+        let (kind, author, text, monitors, monitor, provider, model, output, message) =
+            encode_kind(&item.kind);
+
+        self.conn.execute(
+            "INSERT INTO feed_items
+                (id, at, kind, author, text, monitors, monitor, provider, model, output, message, url, seen)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(id) DO UPDATE SET
+                at = excluded.at,
+                kind = excluded.kind,
+                author = excluded.author,
+                text = excluded.text,
+                monitors = excluded.monitors,
+                monitor = excluded.monitor,
+                provider = excluded.provider,
+                model = excluded.model,
+                output = excluded.output,
+                message = excluded.message,
+                url = excluded.url,
+                seen = excluded.seen",
+            params![
+                item.id.to_string(),
+                item.at.with_timezone(&Utc).to_rfc3339(),
+                kind,
+                author,
+                text,
+                monitors,
+                monitor,
+                provider,
+                model,
+                output,
+                message,
+                item.url,
+                item.seen as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Remove a feed item, e.g. a streaming analysis placeholder that never
+    /// produced output.
+    pub fn remove(&self, id: Uuid) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM feed_items WHERE id = ?1", params![id.to_string()])?;
+        Ok(())
+    }
+
+    /// Remove all feed history, e.g. when the user clears the feed pane.
+    pub fn clear(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM feed_items", [])?;
+        Ok(())
+    }
+
+    /// The `limit` most recent feed items, newest first — ready to seed
+    /// `App::feed` directly.
+    pub fn recent(&self, limit: usize) -> Result<Vec<FeedItem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, at, kind, author, text, monitors, monitor, provider, model, output, message, url, seen
+             FROM feed_items ORDER BY at DESC LIMIT ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![limit as i64], |row| {
+                let id: String = row.get(0)?;
+                let at: String = row.get(1)?;
+                let kind: String = row.get(2)?;
+                let author: Option<String> = row.get(3)?;
+                let text: Option<String> = row.get(4)?;
+                let monitors: Option<String> = row.get(5)?;
+                let monitor: Option<String> = row.get(6)?;
+                let provider: Option<String> = row.get(7)?;
+                let model: Option<String> = row.get(8)?;
+                let output: Option<String> = row.get(9)?;
+                let message: Option<String> = row.get(10)?;
+                let url: Option<String> = row.get(11)?;
+                let seen: i64 = row.get(12)?;
+                Ok((id, at, kind, author, text, monitors, monitor, provider, model, output, message, url, seen))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut items = Vec::with_capacity(rows.len());
+        for (id, at, kind, author, text, monitors, monitor, provider, model, output, message, url, seen) in rows {
+            let Some(item) = decode_row(
+                id, at, kind, author, text, monitors, monitor, provider, model, output, message, url, seen,
+            ) else {
+                continue;
+            };
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn encode_kind(
+    kind: &FeedKind,
+) -> (
+    &'static str,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+) {
+    match kind {
+        FeedKind::Post { author, text, monitors } => (
+            "post",
+            Some(author.clone()),
+            Some(text.clone()),
+            Some(monitors.join("\u{1f}")),
+            None,
+            None,
+            None,
+            None,
+            None,
+        ),
+        FeedKind::Analysis { monitor, provider, model, output } => (
+            "analysis",
+            None,
+            None,
+            None,
+            Some(monitor.clone()),
+            Some(provider.clone()),
+            Some(model.clone()),
+            Some(output.clone()),
+            None,
+        ),
+        FeedKind::Info(message) => ("info", None, None, None, None, None, None, None, Some(message.clone())),
+        FeedKind::Error(message) => ("error", None, None, None, None, None, None, None, Some(message.clone())),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn decode_row(
+    id: String,
+    at: String,
+    kind: String,
+    author: Option<String>,
+    text: Option<String>,
+    monitors: Option<String>,
+    monitor: Option<String>,
+    provider: Option<String>,
+    model: Option<String>,
+    output: Option<String>,
+    message: Option<String>,
+    url: Option<String>,
+    seen: i64,
+) -> Option<FeedItem> {
+    let id = Uuid::parse_str(&id).ok()?;
+    let at: DateTime<Utc> = DateTime::parse_from_rfc3339(&at).ok()?.with_timezone(&Utc);
+    let at: DateTime<Local> = Local.from_utc_datetime(&at.naive_utc());
+
+    let kind = match kind.as_str() {
+        "post" => FeedKind::Post {
+            author: author?,
+            text: text?,
+            monitors: monitors
+                .map(|joined| joined.split('\u{1f}').map(str::to_string).collect())
+                .unwrap_or_default(),
+        },
+        "analysis" => FeedKind::Analysis {
+            monitor: monitor?,
+            provider: provider?,
+            model: model?,
+            output: output.unwrap_or_default(),
+        },
+        "info" => FeedKind::Info(message?),
+        "error" => FeedKind::Error(message?),
+        _ => return None,
+    };
+
+    Some(FeedItem {
+        id,
+        at,
+        kind,
+        url,
+        seen: seen != 0,
+    })
+}
@@ -0,0 +1,263 @@
+//! Outbound webhook forwarding for matched posts and completed analyses, in
+//! the spirit of warpgate's internal log store with forwarding: each
+//! configured forwarder receives a JSON POST for every event type it's
+//! subscribed to, with a small retry-with-backoff so a transient 5xx or
+//! network blip doesn't silently drop an alert.
+//!
+//! A forwarder's `format` picks which [`NotificationSink`] builds the
+//! request body: `generic` POSTs the event fields as flat JSON, `discord`
+//! wraps them in a Discord webhook embed.
+
+use std::{sync::OnceLock, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::{sync::mpsc::UnboundedSender, time::sleep};
+
+use crate::{
+    AppMsg,
+    config::{ForwarderConfig, ForwarderEvent, ForwarderFormat},
+};
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Serialize)]
+struct PostEvent<'a> {
+    event: &'a str,
+    monitor_label: &'a str,
+    author: &'a str,
+    text: &'a str,
+    url: &'a str,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnalysisEvent<'a> {
+    event: &'a str,
+    monitor_label: &'a str,
+    provider: &'a str,
+    model: &'a str,
+    output: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+    timestamp: DateTime<Utc>,
+}
+
+/// Builds the JSON body a forwarder POSTs for a post or analysis event.
+/// `ForwarderFormat` picks which implementation a given `ForwarderConfig`
+/// dispatches through.
+trait NotificationSink {
+    fn post_body(&self, event: &PostEvent) -> Result<Vec<u8>>;
+    fn analysis_body(&self, event: &AnalysisEvent) -> Result<Vec<u8>>;
+}
+
+/// POSTs the event struct as plain flat JSON.
+struct GenericJsonSink;
+
+impl NotificationSink for GenericJsonSink {
+    fn post_body(&self, event: &PostEvent) -> Result<Vec<u8>> {
+        serde_json::to_vec(event).context("failed to serialize forwarder payload")
+    }
+
+    fn analysis_body(&self, event: &AnalysisEvent) -> Result<Vec<u8>> {
+        serde_json::to_vec(event).context("failed to serialize forwarder payload")
+    }
+}
+
+/// Wraps the event in a Discord webhook embed, so the alert renders as a
+/// card in the channel instead of a raw JSON blob.
+struct DiscordEmbedSink;
+
+#[derive(Debug, Serialize)]
+struct DiscordPayload {
+    embeds: Vec<DiscordEmbed>,
+}
+
+#[derive(Debug, Serialize)]
+struct DiscordEmbed {
+    title: String,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    timestamp: DateTime<Utc>,
+    footer: DiscordEmbedFooter,
+}
+
+#[derive(Debug, Serialize)]
+struct DiscordEmbedFooter {
+    text: String,
+}
+
+impl NotificationSink for DiscordEmbedSink {
+    fn post_body(&self, event: &PostEvent) -> Result<Vec<u8>> {
+        let payload = DiscordPayload {
+            embeds: vec![DiscordEmbed {
+                title: format!("{} matched", event.monitor_label),
+                description: format!("@{}: {}", event.author, event.text),
+                url: Some(event.url.to_string()).filter(|url| !url.is_empty()),
+                timestamp: event.timestamp,
+                footer: DiscordEmbedFooter {
+                    text: "x-monitor".to_string(),
+                },
+            }],
+        };
+        serde_json::to_vec(&payload).context("failed to serialize discord embed payload")
+    }
+
+    fn analysis_body(&self, event: &AnalysisEvent) -> Result<Vec<u8>> {
+        let payload = DiscordPayload {
+            embeds: vec![DiscordEmbed {
+                title: format!("{} analysis ({}/{})", event.monitor_label, event.provider, event.model),
+                description: event.output.to_string(),
+                url: event.url.map(str::to_string),
+                timestamp: event.timestamp,
+                footer: DiscordEmbedFooter {
+                    text: "x-monitor".to_string(),
+                },
+            }],
+        };
+        serde_json::to_vec(&payload).context("failed to serialize discord embed payload")
+    }
+}
+
+fn sink_for(format: ForwarderFormat) -> Box<dyn NotificationSink> {
+    match format {
+        ForwarderFormat::Generic => Box::new(GenericJsonSink),
+        ForwarderFormat::Discord => Box::new(DiscordEmbedSink),
+    }
+}
+
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Forward a matched post to every configured forwarder subscribed to `post`
+/// events, one spawned task per forwarder.
+pub fn spawn_post_forwarders(
+    forwarders: &[ForwarderConfig],
+    tx: &UnboundedSender<AppMsg>,
+    monitor_label: String,
+    author: String,
+    text: String,
+    url: String,
+) {
+    for forwarder in forwarders
+        .iter()
+        .filter(|forwarder| forwarder.events.contains(&ForwarderEvent::Post))
+        .cloned()
+    {
+        let tx = tx.clone();
+        let monitor_label = monitor_label.clone();
+        let author = author.clone();
+        let text = text.clone();
+        let url = url.clone();
+        tokio::spawn(async move {
+            let event = PostEvent {
+                event: "post",
+                monitor_label: &monitor_label,
+                author: &author,
+                text: &text,
+                url: &url,
+                timestamp: Utc::now(),
+            };
+            let body = match sink_for(forwarder.format).post_body(&event) {
+                Ok(body) => body,
+                Err(error) => {
+                    let _ = tx.send(AppMsg::Error(format!(
+                        "forwarder {} failed: {error}",
+                        forwarder.url
+                    )));
+                    return;
+                }
+            };
+            if let Err(error) = send_with_retry(&forwarder, body).await {
+                let _ = tx.send(AppMsg::Error(format!(
+                    "forwarder {} failed: {error}",
+                    forwarder.url
+                )));
+            }
+        });
+    }
+}
+
+/// Forward a completed analysis to every configured forwarder subscribed to
+/// `analysis` events, one spawned task per forwarder.
+pub fn spawn_analysis_forwarders(
+    forwarders: &[ForwarderConfig],
+    tx: &UnboundedSender<AppMsg>,
+    monitor_label: String,
+    provider: String,
+    model: String,
+    output: String,
+    url: Option<String>,
+) {
+    for forwarder in forwarders
+        .iter()
+        .filter(|forwarder| forwarder.events.contains(&ForwarderEvent::Analysis))
+        .cloned()
+    {
+        let tx = tx.clone();
+        let monitor_label = monitor_label.clone();
+        let provider = provider.clone();
+        let model = model.clone();
+        let output = output.clone();
+        let url = url.clone();
+        tokio::spawn(async move {
+            let event = AnalysisEvent {
+                event: "analysis",
+                monitor_label: &monitor_label,
+                provider: &provider,
+                model: &model,
+                output: &output,
+                url: url.as_deref(),
+                timestamp: Utc::now(),
+            };
+            let body = match sink_for(forwarder.format).analysis_body(&event) {
+                Ok(body) => body,
+                Err(error) => {
+                    let _ = tx.send(AppMsg::Error(format!(
+                        "forwarder {} failed: {error}",
+                        forwarder.url
+                    )));
+                    return;
+                }
+            };
+            if let Err(error) = send_with_retry(&forwarder, body).await {
+                let _ = tx.send(AppMsg::Error(format!(
+                    "forwarder {} failed: {error}",
+                    forwarder.url
+                )));
+            }
+        });
+    }
+}
+
+async fn send_with_retry(forwarder: &ForwarderConfig, body: Vec<u8>) -> Result<()> {
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+
+        let mut request = http_client()
+            .post(&forwarder.url)
+            .header("content-type", "application/json")
+            .body(body.clone());
+        if let Some(auth_header) = &forwarder.auth_header {
+            request = request.header("authorization", auth_header.clone());
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if attempt < MAX_ATTEMPTS && response.status().is_server_error() => {
+                sleep(BASE_DELAY * 2u32.pow(attempt - 1)).await;
+            }
+            Ok(response) => anyhow::bail!("forwarder returned {}", response.status()),
+            Err(_error) if attempt < MAX_ATTEMPTS => {
+                sleep(BASE_DELAY * 2u32.pow(attempt - 1)).await;
+            }
+            Err(error) => return Err(error).context("forwarder request failed"),
+        }
+    }
+}
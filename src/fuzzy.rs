@@ -0,0 +1,59 @@
+//! Lightweight fuzzy subsequence matcher backing the filter overlay. No
+//! external dependency: a left-to-right scan that requires every query
+//! character to appear in order, with bonuses for contiguous runs and
+//! word-boundary starts so `"jdoe"` ranks `"@jdoe"` above `"janedoe99"`.
+
+/// Separators that mark the start of a "word" within a candidate string.
+const WORD_SEPARATORS: [char; 4] = [' ', '@', '_', ':'];
+
+/// A successful match: the total score plus the character indices (into the
+/// candidate string, by `chars()` position) that were matched, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    pub indices: Vec<usize>,
+}
+
+/// Score `candidate` against `query` (case-insensitive). Returns `None` if
+/// `candidate` doesn't contain every character of `query` in order. An empty
+/// query matches everything with a score of `0`.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let query_chars: Vec<char> = query.trim().chars().map(|ch| ch.to_ascii_lowercase()).collect();
+    if query_chars.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: Vec::new(),
+        });
+    }
+
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let mut indices = Vec::with_capacity(query_chars.len());
+    let mut score = 0i32;
+    let mut query_idx = 0usize;
+    let mut prev_matched = false;
+
+    for (idx, ch) in candidate_chars.iter().enumerate() {
+        if query_idx >= query_chars.len() {
+            break;
+        }
+        if ch.to_ascii_lowercase() != query_chars[query_idx] {
+            prev_matched = false;
+            continue;
+        }
+
+        score += 1;
+        let at_word_boundary = idx == 0 || WORD_SEPARATORS.contains(&candidate_chars[idx - 1]);
+        if at_word_boundary {
+            score += 3;
+        }
+        if prev_matched {
+            score += 2;
+        }
+
+        indices.push(idx);
+        prev_matched = true;
+        query_idx += 1;
+    }
+
+    (query_idx == query_chars.len()).then_some(FuzzyMatch { score, indices })
+}
@@ -0,0 +1,113 @@
+//! External command hooks, fired when a new post or completed analysis
+//! lands in the feed. Spawned detached from the UI so a slow or hung hook
+//! script never blocks the TUI; following the convention xplr uses for its
+//! spawned commands, context is handed over two ways at once: `XMON_*`
+//! environment variables for simple shell one-liners, and the full
+//! [`StreamPost`]/[`FeedItem`] as JSON on stdin for anything that wants
+//! structured data (`jq` pipelines, webhook forwarders, etc).
+
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::{
+    config::HooksConfig,
+    models::{FeedItem, FeedKind, StreamPost},
+};
+
+/// Run `command` for a newly matched post. `command` is either a monitor's
+/// own `on_match` override or the global `hooks.on_post` default; callers
+/// resolve which applies before calling this.
+pub async fn run_post_hook(
+    command: Option<String>,
+    post: StreamPost,
+    monitors: Vec<String>,
+    url: String,
+) -> Result<()> {
+    let Some(command) = non_empty(command) else {
+        return Ok(());
+    };
+
+    let author = post
+        .author_username
+        .clone()
+        .or_else(|| post.author_id.clone())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    run_hook(
+        &command,
+        &[
+            ("XMON_AUTHOR", author),
+            ("XMON_TEXT", post.text.clone()),
+            ("XMON_URL", url),
+            ("XMON_MONITORS", monitors.join(",")),
+            ("XMON_POST_ID", post.id.clone()),
+            ("XMON_MATCHED_TAGS", post.matching_tags.join(",")),
+        ],
+        &post,
+    )
+    .await
+}
+
+/// Run `hooks.on_analysis`, if configured, for a completed AI analysis.
+pub async fn run_analysis_hook(hooks: HooksConfig, item: FeedItem) -> Result<()> {
+    let Some(command) = non_empty(hooks.on_analysis) else {
+        return Ok(());
+    };
+    let FeedKind::Analysis {
+        monitor,
+        provider,
+        model,
+        output,
+    } = &item.kind
+    else {
+        return Ok(());
+    };
+
+    run_hook(
+        &command,
+        &[
+            ("XMON_MONITORS", monitor.clone()),
+            ("XMON_PROVIDER", provider.clone()),
+            ("XMON_MODEL", model.clone()),
+            ("XMON_TEXT", output.clone()),
+            ("XMON_URL", item.url.clone().unwrap_or_default()),
+        ],
+        &item,
+    )
+    .await
+}
+
+fn non_empty(command: Option<String>) -> Option<String> {
+    command.filter(|command| !command.trim().is_empty())
+}
+
+async fn run_hook(command: &str, env: &[(&str, String)], payload: &impl Serialize) -> Result<()> {
+    let stdin_payload =
+        serde_json::to_vec(payload).context("failed to serialize hook payload")?;
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(env.iter().map(|(key, value)| (*key, value.clone())))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to spawn hook command `{command}`"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(&stdin_payload).await;
+    }
+
+    let status = child
+        .wait()
+        .await
+        .with_context(|| format!("hook command `{command}` could not be waited on"))?;
+    if !status.success() {
+        anyhow::bail!("hook command `{command}` exited with {status}");
+    }
+    Ok(())
+}
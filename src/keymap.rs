@@ -0,0 +1,134 @@
+//! User-remappable keybindings for the main feed/monitors view: actions are
+//! resolved through a [`Keymap`] built from `AppConfig`'s `keymap` overrides
+//! layered on top of the built-in defaults, instead of being hardcoded in
+//! `handle_key`, so unfamiliar or conflicting keys can be remapped without
+//! recompiling.
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::config::{Action, KeyBinding};
+
+/// Resolves `(KeyCode, KeyModifiers)` to an [`Action`] for the main view.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<(KeyCode, KeyModifiers), Action>,
+}
+
+impl Keymap {
+    /// Builds a keymap from the built-in defaults, overridden by any entries
+    /// in `overrides` with a recognized key spec. Entries with an
+    /// unparseable `key` are ignored so a typo in config doesn't crash the
+    /// app; the default binding, if any, stays in place.
+    pub fn from_config(overrides: &[KeyBinding]) -> Self {
+        let mut bindings = default_bindings();
+        for entry in overrides {
+            if let Some(key) = parse_key_spec(&entry.key) {
+                bindings.insert(key, entry.action);
+            }
+        }
+        Self { bindings }
+    }
+
+    pub fn resolve(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+        self.bindings.get(&(code, modifiers)).copied()
+    }
+
+    /// The key spec string currently bound to `action`, for footer/help text.
+    /// Empty if no key resolves to it (e.g. an override replaced it).
+    pub fn hint_for(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| **bound == action)
+            .map(|(key, _)| format_key(*key))
+            .unwrap_or_default()
+    }
+}
+
+fn default_bindings() -> HashMap<(KeyCode, KeyModifiers), Action> {
+    use Action::*;
+
+    let mut map = HashMap::new();
+    let mut bind = |code: KeyCode, action: Action| {
+        map.insert((code, KeyModifiers::NONE), action);
+    };
+
+    bind(KeyCode::Char('q'), Quit);
+    bind(KeyCode::Tab, ToggleFocus);
+    bind(KeyCode::Up, MoveUp);
+    bind(KeyCode::Down, MoveDown);
+    bind(KeyCode::Char('a'), AddMonitor);
+    bind(KeyCode::Char('e'), EditMonitor);
+    bind(KeyCode::Char('s'), ToggleActivation);
+    bind(KeyCode::Char('d'), Delete);
+    bind(KeyCode::Char('r'), Reconnect);
+    bind(KeyCode::Char('x'), TerminateAll);
+    bind(KeyCode::Char('o'), OpenUrl);
+    bind(KeyCode::Char('c'), ClearFeed);
+    map
+}
+
+/// Parses a spec like `"q"`, `"tab"`, `"up"`, or `"ctrl+r"` into a key code
+/// and modifier set. Returns `None` for anything it doesn't recognize.
+pub fn parse_key_spec(spec: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut parts: Vec<&str> = spec.split('+').collect();
+    let key_part = parts.pop()?;
+
+    let mut modifiers = KeyModifiers::NONE;
+    for part in parts {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+            "alt" => modifiers |= KeyModifiers::ALT,
+            "shift" => modifiers |= KeyModifiers::SHIFT,
+            _ => return None,
+        }
+    }
+
+    let code = match key_part.to_ascii_lowercase().as_str() {
+        "tab" => KeyCode::Tab,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "backspace" => KeyCode::Backspace,
+        _ => {
+            let mut chars = key_part.chars();
+            let ch = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(ch)
+        }
+    };
+
+    Some((code, modifiers))
+}
+
+fn format_key((code, modifiers): (KeyCode, KeyModifiers)) -> String {
+    let mut parts = Vec::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        parts.push("ctrl".to_string());
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        parts.push("alt".to_string());
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        parts.push("shift".to_string());
+    }
+    parts.push(match code {
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Char(ch) => ch.to_string(),
+        _ => "?".to_string(),
+    });
+    parts.join("+")
+}
@@ -1,34 +1,54 @@
 mod ai;
 mod app;
+mod clipboard;
 mod config;
+mod diff;
+mod feed_store;
+mod forwarders;
+mod fuzzy;
+mod hooks;
+mod keymap;
 mod models;
+mod notifications;
+mod redis_stream;
+mod rss;
+mod scripting;
+mod secrets;
+mod store;
 mod target_files;
+mod theme;
 mod ui;
 mod x_api;
+mod yaml_syntax;
 
 use std::{
+    collections::HashMap,
     fs::{self, File},
     io::{self, BufWriter, Stdout, Write},
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
 use ai::AiClient;
 use anyhow::{Context, Result};
-use app::{App, PendingMonitor};
+use app::{App, FocusPane, PendingMonitor};
 use chrono::{Local, Utc};
+use config::Action;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use dotenvy::dotenv;
-use models::{Monitor, StreamPost};
+use models::{FeedKind, Monitor, MonitorKind, StreamPost};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use ratatui::{Terminal, backend::CrosstermBackend};
+use serde::Serialize;
 use target_files::TargetFileMonitor;
 use tokio::sync::{mpsc, watch};
 use uuid::Uuid;
-use x_api::XApiClient;
+use x_api::{XApiClient, XToolRegistry};
 
 #[derive(Debug, Clone)]
 enum AppMsg {
@@ -46,19 +66,37 @@ enum AppMsg {
     MonitorDeactivated(Result<(Uuid, String), String>),
     MonitorDeleted(Result<(Uuid, String), String>),
     MonitorReconnected(Result<(Uuid, String, String), String>),
-    AnalysisCompleted {
+    AnalysisDelta {
+        item_id: Uuid,
+        delta: String,
+    },
+    AnalysisComplete {
+        item_id: Uuid,
+    },
+    TargetFilesChanged,
+    AnalysisFailed {
+        item_id: Uuid,
         monitor_label: String,
         provider: String,
         model: String,
-        output: Result<String, String>,
-        url: Option<String>,
+        error: String,
     },
 }
 
+/// Output format for the session log. Plain text is the legacy default; JSON
+/// emits one self-describing event object per line for machine consumption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 #[derive(Debug, Default)]
 struct CliArgs {
     log_session: bool,
     log_file: Option<PathBuf>,
+    log_format: LogFormat,
 }
 
 #[derive(Debug)]
@@ -66,6 +104,30 @@ struct SessionLogger {
     path: PathBuf,
     writer: BufWriter<File>,
     last_feed_id: Option<Uuid>,
+    format: LogFormat,
+}
+
+/// A single structured session-log event, written as one JSON object per line
+/// when `--log-format json` is set. Fields irrelevant to a given `event_type`
+/// are omitted rather than emitted as `null`.
+#[derive(Debug, Serialize)]
+struct LogEvent<'a> {
+    ts: String,
+    event_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    monitor_label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<&'a str>,
 }
 
 impl SessionLogger {
@@ -82,10 +144,10 @@ impl SessionLogger {
             None
         };
 
-        path.map(Self::new).transpose()
+        path.map(|path| Self::new(path, cli.log_format)).transpose()
     }
 
-    fn new(path: PathBuf) -> Result<Self> {
+    fn new(path: PathBuf, format: LogFormat) -> Result<Self> {
         if let Some(parent) = path.parent() {
             if !parent.as_os_str().is_empty() {
                 fs::create_dir_all(parent).with_context(|| {
@@ -100,6 +162,7 @@ impl SessionLogger {
             path,
             writer: BufWriter::new(file),
             last_feed_id: None,
+            format,
         })
     }
 
@@ -108,11 +171,36 @@ impl SessionLogger {
     }
 
     fn log_line(&mut self, line: &str) -> Result<()> {
-        let now = Local::now().format("%Y-%m-%d %H:%M:%S");
-        writeln!(self.writer, "[{now}] {line}").context("failed to write to session log")?;
+        match self.format {
+            LogFormat::Text => {
+                let now = Local::now().format("%Y-%m-%d %H:%M:%S");
+                writeln!(self.writer, "[{now}] {line}")
+                    .context("failed to write to session log")?;
+            }
+            LogFormat::Json => {
+                let event = LogEvent {
+                    ts: Utc::now().to_rfc3339(),
+                    event_type: "session",
+                    monitor_label: None,
+                    author: None,
+                    provider: None,
+                    model: None,
+                    text: None,
+                    url: None,
+                    message: Some(line),
+                };
+                self.write_json(&event)?;
+            }
+        }
         self.writer.flush().context("failed to flush session log")
     }
 
+    fn write_json(&mut self, event: &LogEvent<'_>) -> Result<()> {
+        let encoded =
+            serde_json::to_string(event).context("failed to serialize session log event")?;
+        writeln!(self.writer, "{encoded}").context("failed to write to session log")
+    }
+
     fn flush_new_feed_items(&mut self, app: &App) -> Result<()> {
         let ordered = app.feed.iter().rev().collect::<Vec<_>>();
 
@@ -127,16 +215,149 @@ impl SessionLogger {
         };
 
         for item in ordered.iter().skip(start_index) {
-            let mut line = item.summary();
-            if let Some(url) = &item.url {
-                line.push_str(&format!(" | URL: {url}"));
+            match self.format {
+                LogFormat::Text => {
+                    let mut line = item.summary();
+                    if let Some(url) = &item.url {
+                        line.push_str(&format!(" | URL: {url}"));
+                    }
+                    self.log_line(&line)?;
+                }
+                LogFormat::Json => {
+                    let event = feed_item_event(item);
+                    self.write_json(&event)?;
+                    self.writer.flush().context("failed to flush session log")?;
+                }
             }
-            self.log_line(&line)?;
         }
 
         self.last_feed_id = ordered.last().map(|item| item.id);
         Ok(())
     }
+
+    /// Drain `app.lifecycle_log`, writing one JSON event per entry in
+    /// `LogFormat::Json` mode. In `LogFormat::Text` mode the equivalent
+    /// human-readable line already went out through `push_info`/`push_error`
+    /// and `flush_new_feed_items`, so entries are just discarded here.
+    fn flush_new_lifecycle_events(&mut self, app: &mut App) -> Result<()> {
+        for event in app.lifecycle_log.drain(..) {
+            if self.format != LogFormat::Json {
+                continue;
+            }
+            self.write_json(&lifecycle_event_log(&event))?;
+            self.writer.flush().context("failed to flush session log")?;
+        }
+        Ok(())
+    }
+}
+
+/// Translate a [`models::LifecycleEvent`] into its structured log representation.
+fn lifecycle_event_log(event: &models::LifecycleEvent) -> LogEvent<'_> {
+    let ts = Utc::now().to_rfc3339();
+    let blank = || LogEvent {
+        ts: ts.clone(),
+        event_type: "",
+        monitor_label: None,
+        author: None,
+        provider: None,
+        model: None,
+        text: None,
+        url: None,
+        message: None,
+    };
+
+    match event {
+        models::LifecycleEvent::MonitorAdded(label) => LogEvent {
+            event_type: "monitor_added",
+            monitor_label: Some(label),
+            ..blank()
+        },
+        models::LifecycleEvent::MonitorActivated(label) => LogEvent {
+            event_type: "monitor_activated",
+            monitor_label: Some(label),
+            ..blank()
+        },
+        models::LifecycleEvent::MonitorDeactivated(label) => LogEvent {
+            event_type: "monitor_deactivated",
+            monitor_label: Some(label),
+            ..blank()
+        },
+        models::LifecycleEvent::MonitorDeleted(label) => LogEvent {
+            event_type: "monitor_deleted",
+            monitor_label: Some(label),
+            ..blank()
+        },
+        models::LifecycleEvent::StreamConnected => LogEvent {
+            event_type: "stream_connected",
+            ..blank()
+        },
+        models::LifecycleEvent::StreamDisconnected => LogEvent {
+            event_type: "stream_disconnected",
+            ..blank()
+        },
+    }
+}
+
+/// Translate a [`FeedItem`] into its structured log representation.
+fn feed_item_event(item: &models::FeedItem) -> LogEvent<'_> {
+    let ts = item.at.to_rfc3339();
+    let url = item.url.as_deref();
+
+    match &item.kind {
+        FeedKind::Post {
+            author,
+            text,
+            monitors,
+        } => LogEvent {
+            ts,
+            event_type: "post",
+            monitor_label: monitors.first().map(String::as_str),
+            author: Some(author),
+            provider: None,
+            model: None,
+            text: Some(text),
+            url,
+            message: None,
+        },
+        FeedKind::Analysis {
+            monitor,
+            provider,
+            model,
+            output,
+        } => LogEvent {
+            ts,
+            event_type: "analysis",
+            monitor_label: Some(monitor),
+            author: None,
+            provider: Some(provider),
+            model: Some(model),
+            text: Some(output),
+            url,
+            message: None,
+        },
+        FeedKind::Info(message) => LogEvent {
+            ts,
+            event_type: "info",
+            monitor_label: None,
+            author: None,
+            provider: None,
+            model: None,
+            text: None,
+            url,
+            message: Some(message),
+        },
+        FeedKind::Error(message) => LogEvent {
+            ts,
+            event_type: "error",
+            monitor_label: None,
+            author: None,
+            provider: None,
+            model: None,
+            text: None,
+            url,
+            message: Some(message),
+        },
+    }
 }
 
 fn parse_cli_args() -> Result<CliArgs> {
@@ -150,6 +371,14 @@ fn parse_cli_args() -> Result<CliArgs> {
                 let value = args.next().context("--log-file requires a path argument")?;
                 cli.log_file = Some(PathBuf::from(value));
             }
+            "--log-format" => {
+                let value = args.next().context("--log-format requires 'text' or 'json'")?;
+                cli.log_format = match value.as_str() {
+                    "text" => LogFormat::Text,
+                    "json" => LogFormat::Json,
+                    _ => anyhow::bail!("--log-format must be 'text' or 'json', got '{value}'"),
+                };
+            }
             "-h" | "--help" => {
                 print_usage();
                 std::process::exit(0);
@@ -166,11 +395,12 @@ fn parse_cli_args() -> Result<CliArgs> {
 fn print_usage() {
     println!("x-monitor");
     println!("Usage:");
-    println!("  cargo run -- [--log-session | --log-file <path>]");
+    println!("  cargo run -- [--log-session | --log-file <path>] [--log-format <text|json>]");
     println!();
     println!("Options:");
     println!("  --log-session      Write log to ./logs/session-YYYYMMDD-HHMMSS.log");
     println!("  --log-file <path>  Write log to a custom file path");
+    println!("  --log-format <fmt> Log format: 'text' (default) or 'json' (one event per line)");
     println!("  -h, --help         Show this help");
 }
 
@@ -190,6 +420,11 @@ fn default_session_log_path() -> Result<PathBuf> {
 
 fn flush_session_logs(app: &mut App, session_logger: &mut Option<SessionLogger>) {
     let Some(logger) = session_logger.as_mut() else {
+        // No `--log-session`/`--log-file` logger to drain into, but
+        // `App::log_lifecycle` keeps pushing regardless of whether one is
+        // active — drop the backlog here so it doesn't grow unbounded for
+        // the life of the process.
+        app.lifecycle_log.clear();
         return;
     };
 
@@ -197,6 +432,13 @@ fn flush_session_logs(app: &mut App, session_logger: &mut Option<SessionLogger>)
         let message = format!("session logging disabled: {error}");
         *session_logger = None;
         app.push_error(message);
+        return;
+    }
+
+    if let Err(error) = logger.flush_new_lifecycle_events(app) {
+        let message = format!("session logging disabled: {error}");
+        *session_logger = None;
+        app.push_error(message);
     }
 }
 
@@ -209,18 +451,23 @@ async fn main() -> Result<()> {
     let (mut config, config_path, created_default_config) = config::AppConfig::load()?;
     let config_dir_result = prepare_monitor_config_dir(&mut config);
 
-    let state_path = if config.state_path.is_relative() {
+    let store_path = if config.state_path.is_relative() {
         std::env::current_dir()?.join(&config.state_path)
     } else {
         config.state_path.clone()
     };
 
-    let monitors = App::load_store(&state_path).unwrap_or_else(|error| {
-        eprintln!("failed to load state: {error}");
+    let store = App::open_store(&store_path).context("failed to open monitor store")?;
+    let monitors = store.load_all().unwrap_or_else(|error| {
+        eprintln!("failed to load monitor store: {error}");
         Vec::new()
     });
 
-    let mut app = App::new(config.clone(), state_path, monitors);
+    let feed_store_path = store_path.join("feed.sqlite3");
+    let feed_store =
+        App::open_feed_store(&feed_store_path).context("failed to open feed history store")?;
+
+    let mut app = App::new(config.clone(), config_path.clone(), store, feed_store, monitors);
 
     if let Err(error) = config_dir_result {
         app.push_error(format!(
@@ -255,6 +502,20 @@ async fn main() -> Result<()> {
 
     let (msg_tx, mut msg_rx) = mpsc::unbounded_channel::<AppMsg>();
 
+    let redis_tx = config
+        .redis
+        .clone()
+        .map(|redis_config| redis_stream::spawn(redis_config, msg_tx.clone()));
+
+    // Kept alive for the life of the app; dropping it stops delivery.
+    let _target_file_watcher = match start_target_file_watcher(&config.monitor_config_dir, msg_tx.clone()) {
+        Ok(watcher) => Some(watcher),
+        Err(error) => {
+            app.push_error(format!("target-file watcher disabled: {error}"));
+            None
+        }
+    };
+
     let mut terminal = setup_terminal().context("failed to initialize terminal")?;
 
     let run_result = run_app(
@@ -264,6 +525,7 @@ async fn main() -> Result<()> {
         &mut msg_rx,
         x_client,
         ai_client,
+        redis_tx,
         &mut session_logger,
     )
     .await;
@@ -291,25 +553,39 @@ async fn run_app(
     msg_rx: &mut mpsc::UnboundedReceiver<AppMsg>,
     x_client: Option<XApiClient>,
     ai_client: AiClient,
+    redis_tx: Option<mpsc::UnboundedSender<redis_stream::RedisPostEvent>>,
     session_logger: &mut Option<SessionLogger>,
 ) -> Result<()> {
     let mut stream_shutdown_tx: Option<watch::Sender<bool>> = None;
+    let mut rss_shutdown_txs: HashMap<Uuid, watch::Sender<bool>> = HashMap::new();
     reconcile_stream_connection(app, &x_client, &msg_tx, &mut stream_shutdown_tx);
+    reconcile_rss_polls(app, &msg_tx, &mut rss_shutdown_txs);
     flush_session_logs(app, session_logger);
 
     loop {
         terminal.draw(|frame| ui::render(frame, app))?;
 
         while let Ok(message) = msg_rx.try_recv() {
-            handle_message(app, message, msg_tx.clone(), ai_client.clone());
+            handle_message(
+                app,
+                message,
+                msg_tx.clone(),
+                ai_client.clone(),
+                redis_tx.clone(),
+                x_client.clone(),
+            );
         }
         reconcile_stream_connection(app, &x_client, &msg_tx, &mut stream_shutdown_tx);
+        reconcile_rss_polls(app, &msg_tx, &mut rss_shutdown_txs);
         flush_session_logs(app, session_logger);
 
         if app.should_quit {
             if let Some(shutdown_tx) = stream_shutdown_tx.take() {
                 let _ = shutdown_tx.send(true);
             }
+            for (_, shutdown_tx) in rss_shutdown_txs.drain() {
+                let _ = shutdown_tx.send(true);
+            }
             if let Err(error) = app.save_store() {
                 app.push_error(format!("failed to persist state: {error}"));
             }
@@ -346,7 +622,12 @@ fn reconcile_stream_connection(
             };
             let (shutdown_tx, shutdown_rx) = watch::channel(false);
             let tx = msg_tx.clone();
-            tokio::spawn(client.stream_loop(tx, shutdown_rx));
+            let reconnect = x_api::StreamReconnectConfig {
+                base_secs: app.config.stream_reconnect_base_secs,
+                cap_secs: app.config.stream_reconnect_cap_secs,
+                max_attempts: app.config.stream_reconnect_max_attempts,
+            };
+            tokio::spawn(client.stream_loop(tx, shutdown_rx, reconnect));
             *stream_shutdown_tx = Some(shutdown_tx);
             app.push_info("stream started");
         }
@@ -362,11 +643,56 @@ fn reconcile_stream_connection(
     }
 }
 
+/// Spawns/stops one `rss::poll_loop` task per enabled `MonitorKind::Rss`
+/// monitor, mirroring `reconcile_stream_connection`'s reconciler pattern but
+/// keyed per-monitor rather than as a single global connection.
+fn reconcile_rss_polls(
+    app: &App,
+    msg_tx: &mpsc::UnboundedSender<AppMsg>,
+    rss_shutdown_txs: &mut HashMap<Uuid, watch::Sender<bool>>,
+) {
+    let enabled_rss_ids: Vec<Uuid> = app
+        .monitors
+        .iter()
+        .filter(|monitor| monitor.enabled && monitor.kind == MonitorKind::Rss)
+        .map(|monitor| monitor.id)
+        .collect();
+
+    rss_shutdown_txs.retain(|id, shutdown_tx| {
+        if enabled_rss_ids.contains(id) {
+            true
+        } else {
+            let _ = shutdown_tx.send(true);
+            false
+        }
+    });
+
+    for monitor in &app.monitors {
+        if monitor.enabled
+            && monitor.kind == MonitorKind::Rss
+            && !rss_shutdown_txs.contains_key(&monitor.id)
+        {
+            let (shutdown_tx, shutdown_rx) = watch::channel(false);
+            let interval = Duration::from_secs(app.config.rss_poll_interval_secs);
+            tokio::spawn(rss::poll_loop(
+                monitor.clone(),
+                app.store_handle(),
+                interval,
+                msg_tx.clone(),
+                shutdown_rx,
+            ));
+            rss_shutdown_txs.insert(monitor.id, shutdown_tx);
+        }
+    }
+}
+
 fn handle_message(
     app: &mut App,
     message: AppMsg,
     msg_tx: mpsc::UnboundedSender<AppMsg>,
     ai_client: AiClient,
+    redis_tx: Option<mpsc::UnboundedSender<redis_stream::RedisPostEvent>>,
+    x_client: Option<XApiClient>,
 ) {
     match message {
         AppMsg::Info(info) => app.push_info(info),
@@ -464,18 +790,124 @@ fn handle_message(
                 .cloned()
                 .collect::<Vec<_>>();
 
-            let labels = matched
+            let labels: Vec<String> = matched
                 .iter()
                 .map(|monitor| monitor.label.clone())
                 .collect();
-            app.push_post(&post, labels);
 
+            let url = post.post_url();
+            // A monitor's own `script` override wins over the global
+            // `lua_script`; if several matched monitors set one, the first
+            // wins rather than running every script for one post.
+            let monitor_with_script = matched.iter().find(|monitor| monitor.script.is_some()).cloned();
+            let script_result = match &monitor_with_script {
+                Some(monitor) => app
+                    .script_for(monitor)
+                    .map(|engine| engine.on_post(&post, &labels.join(","), &url)),
+                None => app
+                    .scripting()
+                    .map(|engine| engine.on_post(&post, &labels.join(","), &url)),
+            };
+            let script_decision = match script_result {
+                Some(Ok(decision)) => decision,
+                Some(Err(error)) => {
+                    app.push_error(format!("Lua script error in on_post: {error}"));
+                    None
+                }
+                None => None,
+            };
+            if script_decision
+                .as_ref()
+                .is_some_and(|decision| !decision.keep)
+            {
+                return;
+            }
+
+            // Only the displayed text (feed, notifications, forwarders) is
+            // affected by a script's `text` override; the AI prompt always
+            // sees the original post text.
+            let display_text = script_decision
+                .as_ref()
+                .and_then(|decision| decision.text.clone())
+                .unwrap_or_else(|| post.text.clone());
+
+            app.push_post(&post, labels.clone(), &display_text);
+            let author = post
+                .author_username
+                .clone()
+                .or(post.author_id.clone())
+                .unwrap_or_else(|| "unknown".to_string());
+            for monitor in &matched {
+                app.record_monitor_post(monitor.id);
+                if monitor.notify {
+                    app.notifications
+                        .notify_post(&monitor.label, &author, &display_text, Some(post.post_url()));
+                }
+            }
+
+            for monitor in &matched {
+                let sinks = monitor.sinks.as_deref().unwrap_or(&app.config.forwarders);
+                forwarders::spawn_post_forwarders(
+                    sinks,
+                    &msg_tx,
+                    monitor.label.clone(),
+                    author.clone(),
+                    display_text.clone(),
+                    post.post_url(),
+                );
+            }
+
+            if let Some(tx) = &redis_tx {
+                for monitor in &matched {
+                    let _ = tx.send(redis_stream::RedisPostEvent {
+                        monitor_label: monitor.label.clone(),
+                        author: Some(author.clone()),
+                        text: Some(display_text.clone()),
+                        url: post.post_url(),
+                        rule_tag: monitor.rule_tag.clone(),
+                        ai_summary: None,
+                    });
+                }
+            }
+
+            let global_hook = app.config.hooks.on_post.clone();
+            let mut labels_by_command: HashMap<String, Vec<String>> = HashMap::new();
+            for monitor in &matched {
+                let command = monitor
+                    .on_match
+                    .clone()
+                    .filter(|command| !command.trim().is_empty())
+                    .or_else(|| global_hook.clone());
+                if let Some(command) = command {
+                    labels_by_command
+                        .entry(command)
+                        .or_default()
+                        .push(monitor.label.clone());
+                }
+            }
+            for (command, monitor_labels) in labels_by_command {
+                let tx = msg_tx.clone();
+                let post_for_hook = post.clone();
+                let url = post.post_url();
+                tokio::spawn(async move {
+                    if let Err(error) =
+                        hooks::run_post_hook(Some(command), post_for_hook, monitor_labels, url).await
+                    {
+                        let _ = tx.send(AppMsg::Error(format!("post hook failed: {error}")));
+                    }
+                });
+            }
+
+            let skip_ai = script_decision.as_ref().is_some_and(|decision| decision.skip_ai);
             for monitor in matched {
-                if !monitor.analysis.enabled {
+                if !monitor.analysis.enabled || skip_ai {
                     continue;
                 }
 
-                let provider_name = monitor.analysis.provider.clone();
+                let provider_name = script_decision
+                    .as_ref()
+                    .and_then(|decision| decision.provider.clone())
+                    .unwrap_or_else(|| monitor.analysis.provider.clone());
                 let Some(provider_config) = app.config.provider_by_name(&provider_name) else {
                     app.push_error(format!("AI provider '{}' is not configured", provider_name));
                     continue;
@@ -490,6 +922,14 @@ fn handle_message(
                         base_url: provider_config.base_url.clone(),
                         model: provider_config.model.clone(),
                         api_key,
+                        protocol: provider_config.protocol,
+                        proxy: provider_config.proxy.clone(),
+                        timeout_secs: provider_config.timeout_secs,
+                        temperature: provider_config.temperature,
+                        max_tokens: provider_config.max_tokens,
+                        top_p: provider_config.top_p,
+                        retries: app.config.ai_retries,
+                        retry_base_ms: app.config.ai_retry_base_ms,
                     }
                 } else {
                     app.push_error(format!(
@@ -499,10 +939,15 @@ fn handle_message(
                     continue;
                 };
 
-                let model_id = if monitor.analysis.model.trim().is_empty() {
-                    provider.model.clone()
-                } else {
-                    monitor.analysis.model.trim().to_string()
+                let model_override = script_decision
+                    .as_ref()
+                    .and_then(|decision| decision.model.clone());
+                let model_id = match model_override {
+                    Some(model) if !model.trim().is_empty() => model,
+                    _ if !monitor.analysis.model.trim().is_empty() => {
+                        monitor.analysis.model.trim().to_string()
+                    }
+                    _ => provider.model.clone(),
                 };
                 if model_id.is_empty() {
                     app.push_error(format!(
@@ -519,43 +964,224 @@ fn handle_message(
                 if let Some(api_key) = api_key_override {
                     provider.api_key = api_key;
                 }
+                // Per-monitor generation overrides take precedence over the
+                // provider defaults when present.
+                if let Some(temperature) = monitor.analysis.temperature {
+                    provider.temperature = Some(temperature);
+                }
+                if let Some(max_tokens) = monitor.analysis.max_tokens {
+                    provider.max_tokens = Some(max_tokens);
+                }
+                if let Some(top_p) = monitor.analysis.top_p {
+                    provider.top_p = Some(top_p);
+                }
 
                 let tx = msg_tx.clone();
                 let client = ai_client.clone();
-                let prompt = monitor.analysis.prompt.clone();
+                let prompt = script_decision
+                    .as_ref()
+                    .and_then(|decision| decision.prompt.clone())
+                    .unwrap_or_else(|| monitor.analysis.prompt.clone());
                 let post_text = post.text.clone();
-                let monitor_label = monitor.label.clone();
+                let monitor_label = script_decision
+                    .as_ref()
+                    .and_then(|decision| decision.log_label.clone())
+                    .unwrap_or_else(|| monitor.label.clone());
                 let provider_name_for_msg = provider.name.clone();
                 let model_name = model_id.clone();
                 let url = Some(post.post_url());
 
+                // Stream the analysis into a placeholder feed item, updating it in
+                // place as tokens arrive. If the stream fails before emitting any
+                // text we fall back to the blocking path for providers without SSE.
+                let item_id = app.begin_analysis(
+                    monitor.id,
+                    monitor_label.clone(),
+                    provider_name_for_msg.clone(),
+                    model_name.clone(),
+                    url.clone(),
+                );
+
+                if monitor.analysis.use_tools {
+                    let Some(x_client) = x_client.clone() else {
+                        app.discard_analysis(item_id);
+                        app.push_error(format!(
+                            "analysis skipped for '{monitor_label}' because tool-calling requires an X API bearer token"
+                        ));
+                        continue;
+                    };
+                    let registry = XToolRegistry::new(x_client, &post);
+                    tokio::spawn(async move {
+                        match client
+                            .analyze_post_with_tools(provider, model_id, prompt, post_text, &registry)
+                            .await
+                        {
+                            Ok(text) => {
+                                let _ = tx.send(AppMsg::AnalysisDelta { item_id, delta: text });
+                                let _ = tx.send(AppMsg::AnalysisComplete { item_id });
+                            }
+                            Err(error) => {
+                                let _ = tx.send(AppMsg::AnalysisFailed {
+                                    item_id,
+                                    monitor_label,
+                                    provider: provider_name_for_msg,
+                                    model: model_name,
+                                    error: error.to_string(),
+                                });
+                            }
+                        }
+                    });
+                    continue;
+                }
+
                 tokio::spawn(async move {
-                    let output = client
+                    let mut stream = client.analyze_post_stream(
+                        provider.clone(),
+                        model_id.clone(),
+                        prompt.clone(),
+                        post_text.clone(),
+                    );
+
+                    let mut streamed_any = false;
+                    while let Some(chunk) = stream.recv().await {
+                        match chunk {
+                            Ok(delta) => {
+                                streamed_any = true;
+                                let _ = tx.send(AppMsg::AnalysisDelta { item_id, delta });
+                            }
+                            Err(error) if streamed_any => {
+                                let _ = tx.send(AppMsg::AnalysisFailed {
+                                    item_id,
+                                    monitor_label,
+                                    provider: provider_name_for_msg,
+                                    model: model_name,
+                                    error: error.to_string(),
+                                });
+                                return;
+                            }
+                            Err(_) => break,
+                        }
+                    }
+
+                    // Streaming produced text, so the in-place item is already
+                    // complete; otherwise fall back to one blocking round-trip
+                    // for providers that don't support SSE.
+                    if streamed_any {
+                        let _ = tx.send(AppMsg::AnalysisComplete { item_id });
+                        return;
+                    }
+
+                    match client
                         .analyze_post(provider, model_id, prompt, post_text)
                         .await
-                        .map_err(|error| error.to_string());
-                    let _ = tx.send(AppMsg::AnalysisCompleted {
-                        monitor_label,
-                        provider: provider_name_for_msg,
-                        model: model_name,
-                        output,
-                        url,
-                    });
+                    {
+                        Ok(text) => {
+                            let _ = tx.send(AppMsg::AnalysisDelta {
+                                item_id,
+                                delta: text,
+                            });
+                            let _ = tx.send(AppMsg::AnalysisComplete { item_id });
+                        }
+                        Err(error) => {
+                            let _ = tx.send(AppMsg::AnalysisFailed {
+                                item_id,
+                                monitor_label,
+                                provider: provider_name_for_msg,
+                                model: model_name,
+                                error: error.to_string(),
+                            });
+                        }
+                    }
                 });
             }
         }
-        AppMsg::AnalysisCompleted {
+        AppMsg::AnalysisDelta { item_id, delta } => {
+            app.append_analysis_delta(item_id, &delta);
+        }
+        AppMsg::TargetFilesChanged => {
+            if let Some(result) = app.refresh_target_file_picker() {
+                match result {
+                    Ok(count) => app.push_info(format!(
+                        "target files directory changed on disk; refreshed ({count} file(s))"
+                    )),
+                    Err(error) => app.push_error(format!("failed to refresh target files: {error}")),
+                }
+            }
+
+            notify_monitors_backed_by_changed_files(app);
+        }
+        AppMsg::AnalysisComplete { item_id } => {
+            if let Some(item) = app.feed.iter().find(|item| item.id == item_id).cloned() {
+                if let FeedKind::Analysis {
+                    monitor,
+                    provider,
+                    model,
+                    output,
+                } = &item.kind
+                {
+                    let matched_monitor = app
+                        .analysis_monitor_id(item_id)
+                        .and_then(|monitor_id| app.monitor_by_id(monitor_id))
+                        .cloned();
+                    if matched_monitor
+                        .as_ref()
+                        .is_some_and(|m| m.analysis.notify)
+                    {
+                        app.notifications
+                            .notify_analysis(monitor, output, item.url.clone());
+                    }
+
+                    if let Some(tx) = &redis_tx {
+                        let _ = tx.send(redis_stream::RedisPostEvent {
+                            monitor_label: monitor.clone(),
+                            author: None,
+                            text: None,
+                            url: item.url.clone().unwrap_or_default(),
+                            rule_tag: matched_monitor
+                                .as_ref()
+                                .map(|m| m.rule_tag.clone())
+                                .unwrap_or_default(),
+                            ai_summary: Some(output.clone()),
+                        });
+                    }
+
+                    let sinks = matched_monitor
+                        .and_then(|m| m.sinks)
+                        .unwrap_or_else(|| app.config.forwarders.clone());
+                    forwarders::spawn_analysis_forwarders(
+                        &sinks,
+                        &msg_tx,
+                        monitor.clone(),
+                        provider.clone(),
+                        model.clone(),
+                        output.clone(),
+                        item.url.clone(),
+                    );
+                }
+
+                let hooks = app.config.hooks.clone();
+                if hooks.on_analysis.is_some() {
+                    let tx = msg_tx.clone();
+                    tokio::spawn(async move {
+                        if let Err(error) = hooks::run_analysis_hook(hooks, item).await {
+                            let _ = tx.send(AppMsg::Error(format!("analysis hook failed: {error}")));
+                        }
+                    });
+                }
+            }
+        }
+        AppMsg::AnalysisFailed {
+            item_id,
             monitor_label,
             provider,
             model,
-            output,
-            url,
-        } => match output {
-            Ok(text) => app.push_analysis(monitor_label, provider, model, text, url),
-            Err(error) => app.push_error(format!(
+            error,
+        } => {
+            app.discard_analysis(item_id);
+            app.push_error(format!(
                 "analysis failed for '{monitor_label}' via {provider}:{model}: {error}"
-            )),
-        },
+            ));
+        }
     }
 }
 
@@ -570,28 +1196,94 @@ fn handle_key(
         return;
     }
 
+    if app.prompt_picker.is_some() {
+        handle_prompt_picker_key(app, key_event);
+        return;
+    }
+
     if app.add_form.is_some() {
         handle_add_form_key(app, key_event, msg_tx, x_client);
         return;
     }
 
+    if app.filter_overlay.is_some() {
+        handle_filter_overlay_key(app, key_event);
+        return;
+    }
+
+    if let Some(action) = app.keymap().resolve(key_event.code, key_event.modifiers) {
+        match action {
+            Action::Quit => app.should_quit = true,
+            Action::ToggleFocus => app.toggle_focus(),
+            Action::MoveUp => app.move_selection_up(),
+            Action::MoveDown => app.move_selection_down(),
+            Action::AddMonitor => app.open_add_form(),
+            Action::EditMonitor => edit_selected_monitor(app, msg_tx, x_client),
+            Action::ToggleActivation => toggle_selected_monitor_activation(app, msg_tx, x_client),
+            Action::Delete => delete_selected_monitor(app, msg_tx, x_client),
+            Action::Reconnect => reconnect_selected_monitor(app, msg_tx, x_client),
+            Action::TerminateAll => terminate_all_connections(app, msg_tx, x_client),
+            Action::OpenUrl => open_selected_feed_url(app),
+            Action::ClearFeed => {
+                app.clear_feed();
+                app.status = "Feed cleared".to_string();
+            }
+        }
+        return;
+    }
+
+    // Not (yet) remappable: these aren't in the configurable `Action` set.
     match key_event.code {
-        KeyCode::Char('q') => {
-            app.should_quit = true;
+        KeyCode::Char('y') => yank_selected_feed_item(app, false),
+        KeyCode::Char('Y') => yank_selected_feed_item(app, true),
+        KeyCode::Char('/') => app.open_filter_overlay(),
+        KeyCode::Char('E') => export_selected_monitor(app),
+        KeyCode::Char('m') => {
+            app.mark_all_feed_seen();
+            app.status = "Marked all feed items seen".to_string();
         }
-        KeyCode::Tab => app.toggle_focus(),
-        KeyCode::Up => app.move_selection_up(),
-        KeyCode::Down => app.move_selection_down(),
-        KeyCode::Char('a') => app.open_add_form(),
-        KeyCode::Char('e') => edit_selected_monitor(app, msg_tx, x_client),
-        KeyCode::Char('s') => toggle_selected_monitor_activation(app, msg_tx, x_client),
-        KeyCode::Char('d') => delete_selected_monitor(app, msg_tx, x_client),
-        KeyCode::Char('r') => reconnect_selected_monitor(app, msg_tx, x_client),
-        KeyCode::Char('x') => terminate_all_connections(app, msg_tx, x_client),
-        KeyCode::Char('o') => open_selected_feed_url(app),
-        KeyCode::Char('c') => {
-            app.clear_feed();
-            app.status = "Feed cleared".to_string();
+        _ => {}
+    }
+}
+
+fn export_selected_monitor(app: &mut App) {
+    let Some(monitor) = app.selected_monitor().cloned() else {
+        app.push_info("no monitor selected");
+        return;
+    };
+
+    match target_files::export_monitor_to_yaml(&monitor, &app.config.monitor_config_dir) {
+        Ok(path) => app.push_info(format!("exported '{}' to {}", monitor.label, path.display())),
+        Err(error) => app.push_error(format!("failed to export monitor: {error}")),
+    }
+}
+
+fn handle_filter_overlay_key(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Esc => app.close_filter_overlay(),
+        KeyCode::Up => app.move_filter_overlay_selection(-1),
+        KeyCode::Down => app.move_filter_overlay_selection(1),
+        KeyCode::Backspace => app.filter_overlay_pop_char(),
+        KeyCode::Enter => {
+            if let Some((index, _)) = app.selected_filter_overlay_entry() {
+                let Some(overlay) = app.filter_overlay.as_ref() else {
+                    return;
+                };
+                match overlay.pane {
+                    FocusPane::Monitors => app.selected_monitor = index,
+                    FocusPane::Feed => {
+                        app.selected_feed = index;
+                        app.mark_selected_feed_seen();
+                    }
+                }
+            }
+            app.close_filter_overlay();
+        }
+        KeyCode::Char(ch) => {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                return;
+            }
+            app.filter_overlay_push_char(ch);
         }
         _ => {}
     }
@@ -627,6 +1319,20 @@ fn handle_add_form_key(
         open_target_file_picker(app);
         return;
     }
+    if key_event.code == KeyCode::Char('p')
+        && key_event.modifiers.contains(KeyModifiers::CONTROL)
+        && active_field == Some(8)
+    {
+        app.open_prompt_picker();
+        return;
+    }
+    if key_event.code == KeyCode::Char('k')
+        && key_event.modifiers.contains(KeyModifiers::CONTROL)
+        && active_field == Some(7)
+    {
+        store_api_key_in_keyring(app);
+        return;
+    }
 
     let mut submit_form = false;
     let Some(form) = app.add_form.as_mut() else {
@@ -644,6 +1350,8 @@ fn handle_add_form_key(
                 form.cycle_provider(&app.provider_names, -1);
                 form.apply_provider_defaults(&app.config, &app.provider_names);
             }
+            9 => form.ai_notify = !form.ai_notify,
+            10 => form.notify = !form.notify,
             _ => {}
         },
         KeyCode::Right => match form.field_index {
@@ -653,8 +1361,11 @@ fn handle_add_form_key(
                 form.cycle_provider(&app.provider_names, 1);
                 form.apply_provider_defaults(&app.config, &app.provider_names);
             }
+            9 => form.ai_notify = !form.ai_notify,
+            10 => form.notify = !form.notify,
             _ => {}
         },
+
         KeyCode::Backspace => match form.field_index {
             1 => {
                 form.target.pop();
@@ -677,7 +1388,7 @@ fn handle_add_form_key(
             _ => {}
         },
         KeyCode::Enter => {
-            if form.field_index == 9 {
+            if form.field_index == 11 {
                 submit_form = true;
             } else {
                 form.move_field(1);
@@ -733,6 +1444,9 @@ fn handle_target_file_picker_key(
         }
         KeyCode::Up => app.move_target_file_selection(-1),
         KeyCode::Down => app.move_target_file_selection(1),
+        KeyCode::Char('t') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_preview_mode();
+        }
         KeyCode::Enter => select_target_file(app, msg_tx, x_client),
         _ => {}
     }
@@ -769,6 +1483,8 @@ fn select_target_file(
         return;
     }
 
+    app.record_applied_target_file(entry.path.clone(), entry.raw.clone());
+
     app.push_info(format!(
         "selected YAML target file '{}'; connecting...",
         entry.file_name
@@ -776,6 +1492,56 @@ fn select_target_file(
     submit_monitor_form(app, msg_tx, x_client);
 }
 
+fn handle_prompt_picker_key(app: &mut App, key_event: KeyEvent) {
+    let saving = app
+        .prompt_picker
+        .as_ref()
+        .is_some_and(|picker| picker.save_name_input.is_some());
+
+    if saving {
+        match key_event.code {
+            KeyCode::Esc => app.prompt_picker_cancel_save(),
+            KeyCode::Backspace => app.prompt_picker_pop_char(),
+            KeyCode::Enter => {
+                let prompt_text = app
+                    .add_form
+                    .as_ref()
+                    .map(|form| form.ai_prompt.clone())
+                    .unwrap_or_default();
+                match app.prompt_picker_commit_save(prompt_text) {
+                    Ok(()) => app.push_info("saved prompt to library"),
+                    Err(error) => app.push_error(format!("failed to save prompt: {error}")),
+                }
+            }
+            KeyCode::Char(ch) => {
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    return;
+                }
+                app.prompt_picker_push_char(ch);
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    match key_event.code {
+        KeyCode::Char('q') | KeyCode::Esc => app.close_prompt_picker(),
+        KeyCode::Up => app.move_prompt_picker_selection(-1),
+        KeyCode::Down => app.move_prompt_picker_selection(1),
+        KeyCode::Char('s') => app.prompt_picker_begin_save(),
+        KeyCode::Enter => {
+            if let Some(prompt) = app.selected_prompt_entry().cloned() {
+                if let Some(form) = app.add_form.as_mut() {
+                    form.ai_prompt = prompt.text.clone();
+                }
+                app.close_prompt_picker();
+                app.push_info(format!("inserted prompt '{}'", prompt.name));
+            }
+        }
+        _ => {}
+    }
+}
+
 fn apply_target_file_to_form(app: &mut App, target: &TargetFileMonitor) -> Result<()> {
     let Some(form) = app.add_form.as_mut() else {
         anyhow::bail!("target form is not open");
@@ -812,11 +1578,23 @@ fn apply_target_file_to_form(app: &mut App, target: &TargetFileMonitor) -> Resul
         if let Some(prompt) = &target.ai_prompt {
             form.ai_prompt = prompt.clone();
         }
+        form.ai_temperature = target.ai_temperature;
+        form.ai_max_tokens = target.ai_max_tokens;
+        form.ai_top_p = target.ai_top_p;
+        form.ai_notify = target.ai_notify;
+        form.ai_use_tools = target.ai_use_tools;
     }
+    form.notify = target.notify;
+    form.on_match = target.on_match.clone();
+    form.sinks = target.sinks.clone();
+    form.script = target.script.clone();
 
     Ok(())
 }
 
+/// Note: this still requires a bearer token even for `MonitorKind::Rss`
+/// targets, which never touch the X rule API — the form flow is gated on
+/// `x_client` end to end, and splitting that gate is out of scope here.
 fn submit_monitor_form(
     app: &mut App,
     msg_tx: mpsc::UnboundedSender<AppMsg>,
@@ -989,10 +1767,16 @@ fn cancel_monitor_edit(
 }
 
 async fn create_monitor(client: XApiClient, pending: PendingMonitor) -> Result<Monitor> {
-    let rule_id = client
-        .add_rule(pending.query.clone(), pending.rule_tag.clone())
-        .await
-        .context("x rule creation failed")?;
+    // RSS targets are polled directly (see rss::poll_loop) rather than
+    // matched via an X filtered-stream rule.
+    let rule_id = if pending.kind == MonitorKind::Rss {
+        String::new()
+    } else {
+        client
+            .add_rule(pending.query.clone(), pending.rule_tag.clone())
+            .await
+            .context("x rule creation failed")?
+    };
 
     Ok(Monitor {
         id: pending.id,
@@ -1005,6 +1789,10 @@ async fn create_monitor(client: XApiClient, pending: PendingMonitor) -> Result<M
         rule_tag: pending.rule_tag,
         analysis: pending.analysis,
         created_at: Utc::now(),
+        notify: pending.notify,
+        on_match: pending.on_match,
+        sinks: pending.sinks,
+        script: pending.script,
     })
 }
 
@@ -1105,6 +1893,10 @@ async fn reconnect_monitor_rule(
     client: XApiClient,
     monitor: Monitor,
 ) -> Result<(Uuid, String, String)> {
+    if monitor.kind == MonitorKind::Rss {
+        return Ok((monitor.id, monitor.label, String::new()));
+    }
+
     if !monitor.rule_id.trim().is_empty() {
         if let Err(error) = client.delete_rule(monitor.rule_id.clone()).await {
             if !is_rule_not_found_error(&error) {
@@ -1139,6 +1931,10 @@ async fn activate_monitor_rule(
     client: XApiClient,
     monitor: Monitor,
 ) -> Result<(Uuid, String, String)> {
+    if monitor.kind == MonitorKind::Rss {
+        return Ok((monitor.id, monitor.label, String::new()));
+    }
+
     let new_rule_id = client
         .add_rule(monitor.query.clone(), monitor.rule_tag.clone())
         .await
@@ -1148,6 +1944,10 @@ async fn activate_monitor_rule(
 }
 
 async fn disconnect_monitor_for_edit(client: XApiClient, monitor: Monitor) -> Result<Monitor> {
+    if monitor.kind == MonitorKind::Rss {
+        return Ok(monitor);
+    }
+
     if let Err(error) = client.delete_rule(monitor.rule_id.clone()).await {
         if !is_rule_not_found_error(&error) {
             return Err(error).context("x rule deletion failed before edit");
@@ -1161,6 +1961,10 @@ async fn reconnect_after_edit_exit(
     client: XApiClient,
     monitor: Monitor,
 ) -> Result<(Uuid, String, String)> {
+    if monitor.kind == MonitorKind::Rss {
+        return Ok((monitor.id, monitor.label, String::new()));
+    }
+
     let new_rule_id = client
         .add_rule(monitor.query.clone(), monitor.rule_tag.clone())
         .await
@@ -1218,6 +2022,34 @@ fn open_selected_feed_url(app: &mut App) {
     }
 }
 
+/// Copy the selected feed item to the clipboard: a post's URL by default (or
+/// its raw text when `raw_text` is set, e.g. from the Shift+Y binding), an
+/// analysis's model output, or an info/error line's message.
+fn yank_selected_feed_item(app: &mut App, raw_text: bool) {
+    let Some(item) = app.selected_feed_item() else {
+        app.push_info("no feed item selected");
+        return;
+    };
+
+    let (label, text) = match &item.kind {
+        FeedKind::Post { text, .. } if raw_text => ("post text", text.clone()),
+        FeedKind::Post { text, .. } => match item.url.clone() {
+            Some(url) => ("post URL", url),
+            None => ("post text", text.clone()),
+        },
+        FeedKind::Analysis { output, .. } => ("analysis output", output.clone()),
+        FeedKind::Info(message) | FeedKind::Error(message) => ("message", message.clone()),
+    };
+
+    match app.clipboard().copy(&text) {
+        Ok(()) => app.push_info(format!(
+            "copied {label} via {}",
+            app.clipboard().backend_name()
+        )),
+        Err(error) => app.push_error(format!("failed to copy {label}: {error}")),
+    }
+}
+
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -1234,37 +2066,138 @@ fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result
     Ok(())
 }
 
+/// Resolve a monitor's configured API key field, whether it's a pasted
+/// literal, an env-var reference, or a `keyring:`/`cmd:`/`file:` secret
+/// reference — see [`secrets::SecretRef`].
 fn resolve_api_key_input(input: &str) -> Option<String> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
+    if input.trim().is_empty() {
         return None;
     }
+    secrets::SecretRef::parse(input).resolve()
+}
 
-    if let Some(var_name) = trimmed.strip_prefix('$') {
-        if is_env_var_name(var_name) {
-            return std::env::var(var_name)
-                .ok()
-                .map(|value| value.trim().to_string())
-                .filter(|value| !value.is_empty());
-        }
-        return Some(trimmed.to_string());
+/// Move the add/edit form's API key field out of plaintext and into the OS
+/// keyring (Ctrl-K on the API key field), replacing it with the resulting
+/// `keyring:x-monitor/<account>` reference so the value never has to be
+/// pasted again or end up in an exported target file.
+fn store_api_key_in_keyring(app: &mut App) {
+    let Some(form) = app.add_form.as_ref() else {
+        return;
+    };
+    let value = form.ai_api_key.trim().to_string();
+    let account = if form.display_name.trim().is_empty() {
+        form.selected_provider(&app.provider_names)
+    } else {
+        form.display_name.trim().to_string()
     }
+    .replace('/', "-");
 
-    if is_env_var_name(trimmed) {
-        return std::env::var(trimmed)
-            .ok()
-            .map(|value| value.trim().to_string())
-            .filter(|value| !value.is_empty());
+    if value.is_empty() {
+        app.push_error("API key field is empty — nothing to store in the keyring".to_string());
+        return;
     }
+    if secrets::SecretRef::parse(&value).is_opaque_reference() {
+        app.push_info("API key field is already a secret reference".to_string());
+        return;
+    }
+
+    match secrets::store_in_keyring("x-monitor", &account, &value) {
+        Ok(reference) => {
+            if let Some(form) = app.add_form.as_mut() {
+                form.ai_api_key = reference;
+            }
+            app.push_info(format!("stored API key in the OS keyring as x-monitor/{account}"));
+        }
+        Err(error) => app.push_error(format!("failed to store API key in keyring: {error}")),
+    }
+}
+
+/// Coalescing window for rapid successive filesystem events (e.g. an
+/// editor's write-then-rename save sequence) into a single refresh, so a
+/// single save doesn't trigger a parse storm.
+const TARGET_FILE_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watch `dir` for target-file changes, the way yazi watches a directory for
+/// live updates. `notify` runs the callback on its own background thread, so
+/// it posts straight onto `msg_tx` rather than needing a tokio task. Events
+/// are debounced (gitui's `notify_mutex` pattern: a shared flag gates a
+/// single pending flush) so a burst of writes collapses into one
+/// `AppMsg::TargetFilesChanged`. The returned watcher must be kept alive for
+/// the life of the app; dropping it stops delivery.
+fn start_target_file_watcher(
+    dir: &Path,
+    msg_tx: mpsc::UnboundedSender<AppMsg>,
+) -> Result<RecommendedWatcher> {
+    let flush_pending = Arc::new(Mutex::new(false));
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if event.kind.is_access() {
+            return;
+        }
+
+        let mut pending = flush_pending.lock().unwrap();
+        if *pending {
+            return;
+        }
+        *pending = true;
+        drop(pending);
+
+        let tx = msg_tx.clone();
+        let flush_pending = flush_pending.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(TARGET_FILE_DEBOUNCE);
+            *flush_pending.lock().unwrap() = false;
+            let _ = tx.send(AppMsg::TargetFilesChanged);
+        });
+    })
+    .context("failed to create target-file watcher")?;
 
-    Some(trimmed.to_string())
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("failed to watch monitor config directory {}", dir.display()))?;
+
+    Ok(watcher)
 }
 
-fn is_env_var_name(name: &str) -> bool {
-    !name.is_empty()
-        && name
-            .chars()
-            .all(|ch| ch == '_' || ch.is_ascii_uppercase() || ch.is_ascii_digit())
+/// After the target-file directory changes on disk, check whether any
+/// on-disk file whose `label` matches an existing monitor now disagrees with
+/// that monitor's kind, target or `on_match` command (AI and sink settings
+/// are not compared), and if so nudge the user to re-apply it through
+/// the existing target-file-picker / edit flow rather than auto-applying
+/// (an unattended edit could silently swap out a monitor's target).
+fn notify_monitors_backed_by_changed_files(app: &mut App) {
+    let Ok(entries) = target_files::load_target_file_entries(&app.config.monitor_config_dir) else {
+        return;
+    };
+
+    let mut drifted = Vec::new();
+    for entry in &entries {
+        let Ok(target) = &entry.parsed else {
+            continue;
+        };
+        let Some(label) = &target.label else {
+            continue;
+        };
+        let Some(monitor) = app.monitor_by_label(label) else {
+            continue;
+        };
+
+        let drifted_target = monitor.kind != target.kind
+            || monitor.input_value.trim() != target.target.trim()
+            || monitor.on_match != target.on_match;
+        if drifted_target {
+            drifted.push((entry.file_name.clone(), label.clone()));
+        }
+    }
+
+    for (file_name, label) in drifted {
+        app.push_info(format!(
+            "target file '{file_name}' backing monitor '{label}' changed on disk — add a target and import it from the target file picker to re-apply"
+        ));
+    }
 }
 
 fn prepare_monitor_config_dir(config: &mut config::AppConfig) -> Result<()> {
@@ -1295,9 +2228,26 @@ fn prepare_monitor_config_dir(config: &mut config::AppConfig) -> Result<()> {
 const SAMPLE_TARGET_FILE: &str = r#"label: "Example account watch"
 kind: account
 target: "@handle_1, handle2, @handle_3"
+# Run a command on every matched post, overriding the global `hooks.on_post`
+# for this target only. See `hooks.rs` for the XMON_* environment variables
+# passed through.
+# on_match: "notify-send \"$XMON_MONITORS\" \"$XMON_TEXT\""
+# Outbound webhook sinks for this target only, overriding the global
+# `forwarders` list. `format: discord` wraps the payload in a Discord embed.
+# sinks:
+#   - url: "https://discord.com/api/webhooks/..."
+#     format: discord
+# Lua script run on each matched post before it reaches the feed and AI
+# provider, overriding the global `lua_script` for this target only. See
+# `scripting.rs` for the `on_post(post)` contract.
+# script: "scripts/filter.lua"
 ai:
   enabled: true
   provider: grok
   model: grok-4-1-fast-non-reasoning
   prompt: "Summarize why this post matters and what to watch next."
+  # Let the model call tools (fetch the parent tweet / author timeline)
+  # before answering, via `x_api::XToolRegistry`. Runs as blocking requests,
+  # one per tool-calling round, instead of the usual streamed analysis.
+  # use_tools: true
 "#;
@@ -1,13 +1,18 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, path::PathBuf};
 
 use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::config::ForwarderConfig;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MonitorKind {
     Account,
     Phrase,
+    /// An RSS/Atom feed URL, polled on an interval instead of matched
+    /// against the filtered stream. See `rss::poll_loop`.
+    Rss,
 }
 
 impl MonitorKind {
@@ -15,6 +20,7 @@ impl MonitorKind {
         match self {
             Self::Account => "Account",
             Self::Phrase => "Phrase",
+            Self::Rss => "RSS",
         }
     }
 }
@@ -30,6 +36,21 @@ pub struct AnalysisSettings {
     #[serde(default)]
     pub api_key: String,
     pub prompt: String,
+    /// Per-monitor generation overrides; each falls back to the provider default.
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    /// Fire a desktop notification when an analysis completes.
+    #[serde(default)]
+    pub notify: bool,
+    /// Let the model call tools (fetch the parent tweet / author timeline)
+    /// before answering, via `x_api::XToolRegistry`. Runs as blocking requests,
+    /// one per tool-calling round, instead of the usual streamed analysis.
+    #[serde(default)]
+    pub use_tools: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,28 +66,99 @@ pub struct Monitor {
     pub rule_tag: String,
     pub analysis: AnalysisSettings,
     pub created_at: DateTime<Utc>,
+    /// Fire a desktop notification when a post matches this monitor.
+    #[serde(default)]
+    pub notify: bool,
+    /// External command to run (via `hooks::run_post_hook`) when a post
+    /// matches this monitor, overriding `hooks.on_post` for this monitor only.
+    #[serde(default)]
+    pub on_match: Option<String>,
+    /// Outbound webhook sinks for this monitor's matches and analyses,
+    /// overriding `AppConfig.forwarders` for this monitor only. `None` means
+    /// use the global list.
+    #[serde(default)]
+    pub sinks: Option<Vec<ForwarderConfig>>,
+    /// Lua script run on each matched post before it reaches the feed and AI
+    /// provider, overriding `AppConfig.lua_script` for this monitor only.
+    /// See `scripting::ScriptEngine`.
+    #[serde(default)]
+    pub script: Option<PathBuf>,
 }
 
 fn default_monitor_enabled() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct MonitorStore {
-    pub monitors: Vec<Monitor>,
+/// Number of buckets kept in a [`VolumeSeries`] ring.
+pub const VOLUME_BUCKETS: usize = 60;
+/// Wall-clock span of a single bucket.
+const VOLUME_BUCKET_SECS: i64 = 60;
+
+/// Rolling time-bucketed post counter for one monitor: a fixed-size ring of
+/// [`VOLUME_BUCKETS`] buckets, each spanning `VOLUME_BUCKET_SECS` of
+/// wall-clock time, oldest first. Backs the per-monitor sparkline in the
+/// monitor list, and is persisted alongside the monitor store so activity
+/// history survives a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeSeries {
+    /// Wall-clock start of the current (most recent) bucket.
+    bucket_start: DateTime<Utc>,
+    buckets: Vec<u16>,
 }
 
-#[derive(Debug, Clone)]
+impl VolumeSeries {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            bucket_start: now,
+            buckets: vec![0; VOLUME_BUCKETS],
+        }
+    }
+
+    /// Advance the ring to `now`, zeroing out any buckets that elapsed with
+    /// no activity. A no-op if `now` is still within the current bucket.
+    pub fn advance(&mut self, now: DateTime<Utc>) {
+        let elapsed_secs = (now - self.bucket_start).num_seconds();
+        if elapsed_secs < VOLUME_BUCKET_SECS {
+            return;
+        }
+        let shift = ((elapsed_secs / VOLUME_BUCKET_SECS) as usize).min(VOLUME_BUCKETS);
+        self.buckets.drain(0..shift);
+        self.buckets.extend(std::iter::repeat(0).take(shift));
+        self.bucket_start = now;
+    }
+
+    /// Advance the ring to `now`, then increment the current bucket.
+    pub fn record(&mut self, now: DateTime<Utc>) {
+        self.advance(now);
+        if let Some(last) = self.buckets.last_mut() {
+            *last = last.saturating_add(1);
+        }
+    }
+
+    /// Ordered bucket counts, oldest first.
+    pub fn counts(&self) -> &[u16] {
+        &self.buckets
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct StreamPost {
     pub id: String,
     pub author_id: Option<String>,
     pub author_username: Option<String>,
     pub text: String,
     pub matching_tags: Vec<String>,
+    /// Explicit source URL, set by non-X sources (e.g. `rss::poll_loop`)
+    /// that can't be reconstructed from `id`/`author_username` alone.
+    #[serde(default)]
+    pub url: Option<String>,
 }
 
 impl StreamPost {
     pub fn post_url(&self) -> String {
+        if let Some(url) = &self.url {
+            return url.clone();
+        }
         match &self.author_username {
             Some(username) => format!("https://x.com/{username}/status/{}", self.id),
             None => format!("https://x.com/i/web/status/{}", self.id),
@@ -74,7 +166,7 @@ impl StreamPost {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum FeedKind {
     Post {
         author: String,
@@ -91,15 +183,38 @@ pub enum FeedKind {
     Error(String),
 }
 
+/// Typed monitor-lifecycle / stream-connection events, queued on [`crate::app::App`]
+/// for `SessionLogger` to drain as distinct `event_type`s in the JSON session
+/// log — separate from the free-text lines `App::push_info`/`push_error`
+/// surface in the feed, which only ever log as generic `"info"`/`"error"`.
 #[derive(Debug, Clone)]
+pub enum LifecycleEvent {
+    MonitorAdded(String),
+    MonitorActivated(String),
+    MonitorDeactivated(String),
+    MonitorDeleted(String),
+    StreamConnected,
+    StreamDisconnected,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct FeedItem {
     pub id: Uuid,
     pub at: DateTime<Local>,
     pub kind: FeedKind,
     pub url: Option<String>,
+    /// Whether the user has scrolled onto / selected this item yet. Only
+    /// meaningful for [`FeedKind::Post`]/[`FeedKind::Analysis`] — info/error
+    /// lines don't participate in the unseen count.
+    pub seen: bool,
 }
 
 impl FeedItem {
+    /// Whether this item's kind contributes to the unseen-count/highlighting.
+    pub fn tracks_seen(&self) -> bool {
+        matches!(self.kind, FeedKind::Post { .. } | FeedKind::Analysis { .. })
+    }
+
     pub fn summary(&self) -> String {
         let ts = self.at.format("%H:%M:%S");
         match &self.kind {
@@ -163,6 +278,10 @@ pub fn build_query(kind: &MonitorKind, target: &str) -> anyhow::Result<String> {
                 Ok(trimmed.to_string())
             }
         }
+        // Not used to build an X filtered-stream rule; `rss::poll_loop` fetches
+        // the URL directly. Kept non-empty so it still reads sensibly wherever
+        // a monitor's `query` is surfaced (e.g. the target-file export).
+        MonitorKind::Rss => Ok(trimmed.to_string()),
     }
 }
 
@@ -0,0 +1,126 @@
+//! Native desktop notifications for high-signal matches, in the spirit of
+//! the notification center Zed introduced: a post or analysis only surfaces
+//! here when its monitor is flagged `notify: true`, so running x-monitor in
+//! the background doesn't mean babysitting the feed pane. A burst of hits
+//! for the same monitor coalesces into a single "N new hits" alert instead
+//! of flooding the desktop one notification at a time.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use notify_rust::Notification;
+
+/// More than this many hits for one monitor within [`COALESCE_WINDOW`] and
+/// further notifications fold into a single coalesced alert.
+const COALESCE_THRESHOLD: usize = 3;
+const COALESCE_WINDOW: Duration = Duration::from_secs(30);
+
+struct MonitorBurst {
+    count: usize,
+    window_start: Instant,
+}
+
+/// What [`NotificationCenter::coalesce`] decided for one more hit.
+enum BurstDecision {
+    Single,
+    Coalesced(String),
+    Suppressed,
+}
+
+/// Tracks recent notification bursts per monitor label.
+#[derive(Default)]
+pub struct NotificationCenter {
+    bursts: HashMap<String, MonitorBurst>,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Notify for a newly matched post on a monitor flagged `notify: true`.
+    pub fn notify_post(&mut self, monitor_label: &str, author: &str, text: &str, url: Option<String>) {
+        let body = match self.coalesce(monitor_label) {
+            BurstDecision::Coalesced(summary) => summary,
+            BurstDecision::Single => format!("@{author}: {}", truncate(text, 140)),
+            BurstDecision::Suppressed => return,
+        };
+        self.fire(monitor_label, &body, url);
+    }
+
+    /// Notify for a completed AI analysis on a monitor whose
+    /// `analysis.notify` is set.
+    pub fn notify_analysis(&mut self, monitor_label: &str, output: &str, url: Option<String>) {
+        let body = match self.coalesce(monitor_label) {
+            BurstDecision::Coalesced(summary) => summary,
+            BurstDecision::Single => truncate(output, 140),
+            BurstDecision::Suppressed => return,
+        };
+        self.fire(monitor_label, &body, url);
+    }
+
+    /// Record one more hit for `monitor_label` in the current burst window.
+    /// Returns `Single` up to the threshold, `Coalesced(message)` for the hit
+    /// that first crosses it (sent instead of the caller's own body), and
+    /// `Suppressed` for every later hit in the same window.
+    fn coalesce(&mut self, monitor_label: &str) -> BurstDecision {
+        let now = Instant::now();
+        let burst = self
+            .bursts
+            .entry(monitor_label.to_string())
+            .or_insert_with(|| MonitorBurst {
+                count: 0,
+                window_start: now,
+            });
+
+        if now.duration_since(burst.window_start) > COALESCE_WINDOW {
+            burst.count = 0;
+            burst.window_start = now;
+        }
+        burst.count += 1;
+
+        if burst.count <= COALESCE_THRESHOLD {
+            BurstDecision::Single
+        } else if burst.count == COALESCE_THRESHOLD + 1 {
+            BurstDecision::Coalesced(format!("{} new hits for {monitor_label}", burst.count))
+        } else {
+            BurstDecision::Suppressed
+        }
+    }
+
+    fn fire(&self, title: &str, body: &str, url: Option<String>) {
+        let mut notification = Notification::new();
+        notification.summary(title).body(body);
+        if url.is_some() {
+            notification.action("default", "Open");
+        }
+
+        match notification.show() {
+            Ok(handle) => {
+                let Some(url) = url else { return };
+                // `wait_for_action` blocks, so hand it to its own thread
+                // rather than stalling the event loop for a click that may
+                // never come.
+                std::thread::spawn(move || {
+                    handle.wait_for_action(|action| {
+                        if action == "default" {
+                            let _ = webbrowser::open(&url);
+                        }
+                    });
+                });
+            }
+            Err(error) => eprintln!("failed to show desktop notification: {error}"),
+        }
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let collapsed = text.replace('\n', " ");
+    if collapsed.chars().count() <= max_chars {
+        return collapsed;
+    }
+    let truncated: String = collapsed.chars().take(max_chars).collect();
+    format!("{truncated}…")
+}
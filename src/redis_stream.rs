@@ -0,0 +1,120 @@
+//! Optional Redis Stream fan-out of matched posts, in the spirit of kon and
+//! flodgatt's pooled Redis publishers: a dedicated task, fed by an `mpsc`
+//! channel, `XADD`s every matched post to a Redis Stream so matches survive
+//! restarts and other processes can tail them independently of the TUI. A
+//! slow or unreachable Redis instance only backs up this one channel —
+//! `submit_monitor_form` and the stream reader never block on it.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use bb8_redis::{RedisConnectionManager, bb8::Pool, redis::AsyncCommands};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::sleep,
+};
+
+use crate::{AppMsg, config::RedisConfig};
+
+/// Base delay before the first republish attempt after a failed `XADD`.
+/// Doubled on each consecutive failure, capped at `RECONNECT_CAP`.
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_CAP: Duration = Duration::from_secs(30);
+
+/// One matched post, flattened into the fields an `XADD` entry carries. Two
+/// entries are published per match: the initial one at match time (`author`
+/// and `text` set, `ai_summary` still `None`) and, once the AI analysis for
+/// that post completes, a second update entry carrying just `ai_summary`
+/// (`author`/`text` `None` — the analysis task doesn't have the original
+/// post text in hand) so a consumer tailing the stream sees the summary
+/// without re-fetching the post.
+#[derive(Debug, Clone)]
+pub struct RedisPostEvent {
+    pub monitor_label: String,
+    pub author: Option<String>,
+    pub text: Option<String>,
+    pub url: String,
+    pub rule_tag: String,
+    pub ai_summary: Option<String>,
+}
+
+/// Spawn the dedicated publisher task and return a sender to feed it matched
+/// posts. Dropping the returned sender (e.g. on shutdown) ends the task.
+pub fn spawn(config: RedisConfig, app_tx: UnboundedSender<AppMsg>) -> UnboundedSender<RedisPostEvent> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(run(config, rx, app_tx));
+    tx
+}
+
+async fn run(config: RedisConfig, mut rx: UnboundedReceiver<RedisPostEvent>, app_tx: UnboundedSender<AppMsg>) {
+    let manager = match RedisConnectionManager::new(config.url.clone()) {
+        Ok(manager) => manager,
+        Err(error) => {
+            let _ = app_tx.send(AppMsg::Error(format!(
+                "invalid redis url '{}': {error}",
+                config.url
+            )));
+            return;
+        }
+    };
+
+    let pool = match Pool::builder().build(manager).await {
+        Ok(pool) => pool,
+        Err(error) => {
+            let _ = app_tx.send(AppMsg::Error(format!("failed to build redis pool: {error}")));
+            return;
+        }
+    };
+
+    let mut consecutive_failures = 0u32;
+    while let Some(event) = rx.recv().await {
+        loop {
+            match publish(&pool, &config.stream_key, &event).await {
+                Ok(()) => {
+                    consecutive_failures = 0;
+                    break;
+                }
+                Err(error) => {
+                    let _ = app_tx.send(AppMsg::Error(format!(
+                        "redis XADD to '{}' failed: {error}",
+                        config.stream_key
+                    )));
+                    sleep(backoff_delay(consecutive_failures)).await;
+                    consecutive_failures = consecutive_failures.saturating_add(1);
+                }
+            }
+        }
+    }
+}
+
+async fn publish(
+    pool: &Pool<RedisConnectionManager>,
+    stream_key: &str,
+    event: &RedisPostEvent,
+) -> Result<()> {
+    let mut conn = pool
+        .get()
+        .await
+        .context("failed to check out a pooled redis connection")?;
+
+    let fields: [(&str, &str); 6] = [
+        ("monitor_label", &event.monitor_label),
+        ("author", event.author.as_deref().unwrap_or("")),
+        ("text", event.text.as_deref().unwrap_or("")),
+        ("url", &event.url),
+        ("rule_tag", &event.rule_tag),
+        ("ai_summary", event.ai_summary.as_deref().unwrap_or("")),
+    ];
+
+    conn.xadd::<_, _, _, _, ()>(stream_key, "*", &fields)
+        .await
+        .context("XADD failed")
+}
+
+/// Exponential backoff with no jitter — there's only ever one publisher
+/// task, so there's no herd of concurrent retries to stagger.
+fn backoff_delay(attempt: u32) -> Duration {
+    RECONNECT_BASE
+        .saturating_mul(1u32 << attempt.min(5))
+        .min(RECONNECT_CAP)
+}
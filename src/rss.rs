@@ -0,0 +1,158 @@
+//! RSS/Atom polling for `MonitorKind::Rss` targets, running parallel to the
+//! filtered-stream path: each enabled RSS monitor gets its own poll loop
+//! (spawned/stopped by `reconcile_rss_polls` in main.rs, mirroring how
+//! `reconcile_stream_connection` manages the filtered stream) that fetches
+//! the feed on an interval and emits unseen entries as `AppMsg::StreamPost`,
+//! so they flow through the same matching, notification, hook, forwarder,
+//! and AI-analysis path as an X post — matched purely via the monitor's own
+//! `rule_tag`, since `matching_tags` is populated the same way either way.
+
+use std::{collections::HashSet, time::Duration};
+
+use tokio::{
+    sync::{mpsc::UnboundedSender, watch},
+    time::sleep,
+};
+
+use crate::{
+    AppMsg,
+    models::{Monitor, StreamPost},
+    store::MonitorStore,
+};
+
+/// Timeout for a single feed fetch. Feeds live behind ordinary HTTP servers,
+/// not a long-lived stream, so unlike `x_api`'s client this one always has a
+/// timeout.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Polls `monitor.input_value` (the feed URL) every `interval` until
+/// `shutdown_rx` fires, persisting the seen-entry set in `store` after each
+/// successful poll.
+pub async fn poll_loop(
+    monitor: Monitor,
+    store: MonitorStore,
+    interval: Duration,
+    tx: UnboundedSender<AppMsg>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let http = match reqwest::Client::builder().timeout(FETCH_TIMEOUT).build() {
+        Ok(http) => http,
+        Err(error) => {
+            let _ = tx.send(AppMsg::Error(format!(
+                "failed to build HTTP client for '{}': {error}",
+                monitor.label
+            )));
+            return;
+        }
+    };
+
+    let mut seen = store.load_rss_seen(monitor.id).unwrap_or_default();
+    // The very first poll after a monitor is added seeds the seen-set
+    // without emitting anything, so an existing feed's backlog doesn't
+    // flood the pane on connect.
+    let mut seeded = !seen.is_empty();
+
+    loop {
+        match fetch_and_diff(&http, &monitor, &seen).await {
+            Ok((new_posts, updated_seen)) => {
+                seen = updated_seen;
+                if seeded {
+                    for post in new_posts {
+                        let _ = tx.send(AppMsg::StreamPost(post));
+                    }
+                } else {
+                    seeded = true;
+                }
+
+                if let Err(error) = store.save_rss_seen(monitor.id, &seen) {
+                    let _ = tx.send(AppMsg::Error(format!(
+                        "failed to persist RSS seen-set for '{}': {error}",
+                        monitor.label
+                    )));
+                }
+            }
+            Err(error) => {
+                let _ = tx.send(AppMsg::Error(format!(
+                    "RSS poll failed for '{}': {error}",
+                    monitor.label
+                )));
+            }
+        }
+
+        tokio::select! {
+            _ = shutdown_rx.changed() => break,
+            _ = sleep(interval) => {}
+        }
+    }
+}
+
+async fn fetch_and_diff(
+    http: &reqwest::Client,
+    monitor: &Monitor,
+    seen: &HashSet<String>,
+) -> anyhow::Result<(Vec<StreamPost>, HashSet<String>)> {
+    use anyhow::Context;
+
+    let body = http
+        .get(&monitor.input_value)
+        .send()
+        .await
+        .with_context(|| format!("failed to fetch feed {}", monitor.input_value))?
+        .bytes()
+        .await
+        .context("failed to read feed response body")?;
+
+    let feed = feed_rs::parser::parse(&body[..]).context("failed to parse feed")?;
+
+    let mut updated_seen = seen.clone();
+    let mut new_posts = Vec::new();
+
+    for entry in feed.entries {
+        let id = entry_id(&entry);
+        if seen.contains(&id) {
+            continue;
+        }
+        updated_seen.insert(id.clone());
+
+        let title = entry.title.map(|text| text.content).unwrap_or_default();
+        let summary = entry.summary.map(|text| text.content).unwrap_or_default();
+        let text = match (title.is_empty(), summary.is_empty()) {
+            (false, false) => format!("{title}\n\n{summary}"),
+            (false, true) => title,
+            _ => summary,
+        };
+        let url = entry.links.first().map(|link| link.href.clone());
+
+        new_posts.push(StreamPost {
+            id,
+            author_id: None,
+            author_username: None,
+            text,
+            matching_tags: vec![monitor.rule_tag.clone()],
+            url,
+        });
+    }
+
+    Ok((new_posts, updated_seen))
+}
+
+/// Feeds without a stable entry ID (common with minimal RSS 2.0 feeds) fall
+/// back to hashing the link and title together.
+fn entry_id(entry: &feed_rs::model::Entry) -> String {
+    if !entry.id.trim().is_empty() {
+        return entry.id.clone();
+    }
+
+    let link = entry.links.first().map(|link| link.href.as_str()).unwrap_or("");
+    let title = entry
+        .title
+        .as_ref()
+        .map(|text| text.content.as_str())
+        .unwrap_or("");
+
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    link.hash(&mut hasher);
+    title.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
@@ -0,0 +1,123 @@
+//! Optional Lua scripting hook for custom match filtering and analysis
+//! routing, in the style of xplr and trinitrix's `mlua` integrations: load a
+//! user script once at startup and call its `on_post` function before each
+//! matched post is queued for analysis, so power users can override
+//! keep/drop and AI routing decisions without recompiling.
+
+use std::{path::Path, sync::Mutex};
+
+use anyhow::{Context, Result};
+use mlua::{Function, Lua, Value};
+
+use crate::models::StreamPost;
+
+/// Per-post overrides an `on_post` script can request. `None` fields fall
+/// back to the monitor's own settings.
+#[derive(Debug, Clone)]
+pub struct PostDecision {
+    /// Whether this post should still be pushed into the feed and analyzed.
+    pub keep: bool,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub prompt: Option<String>,
+    /// Overrides the monitor label used for this post's feed/log entries.
+    pub log_label: Option<String>,
+    /// Overrides the post text shown in the feed/notifications/forwarders.
+    /// The AI prompt still receives the original `post.text`.
+    pub text: Option<String>,
+    /// Keeps the post in the feed but skips AI analysis for it.
+    pub skip_ai: bool,
+}
+
+/// Wraps the Lua VM in a mutex so it can be invoked from the main task
+/// without requiring `Lua: Sync` (it isn't).
+pub struct ScriptEngine {
+    lua: Mutex<Lua>,
+}
+
+impl ScriptEngine {
+    /// Load `path` as a Lua script exposing an `on_post(post)` global function.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read Lua script {}", path.display()))?;
+        let lua = Lua::new();
+        lua.load(&source)
+            .exec()
+            .with_context(|| format!("failed to execute Lua script {}", path.display()))?;
+        Ok(Self {
+            lua: Mutex::new(lua),
+        })
+    }
+
+    /// Call `on_post(post)`, if the script defines it, and translate its
+    /// return value into a [`PostDecision`]: `false`/`nil` drops the post,
+    /// a string replaces its display text, and a table can set `keep`,
+    /// `provider`, `model`, `prompt`, `log_label`, `text`, and `skip_ai`.
+    /// Returns `Ok(None)` (no override — keep and analyze as the monitor is
+    /// already configured) if the script doesn't define `on_post` or returns
+    /// something else entirely. Returns `Err` on an actual Lua runtime error
+    /// (e.g. the script calls `error(...)` or indexes a nil); the caller is
+    /// expected to surface that via `app.push_error` rather than let it pass
+    /// for "no override".
+    pub fn on_post(
+        &self,
+        post: &StreamPost,
+        monitor_label: &str,
+        url: &str,
+    ) -> mlua::Result<Option<PostDecision>> {
+        let lua = self.lua.lock().unwrap();
+        let on_post: Function = match lua.globals().get("on_post") {
+            Ok(function) => function,
+            Err(_) => return Ok(None),
+        };
+
+        let table = lua.create_table()?;
+        table.set("text", post.text.clone())?;
+        table.set("author", post.author_username.clone().unwrap_or_default())?;
+        table.set("url", url.to_string())?;
+        table.set("matching_tags", post.matching_tags.clone())?;
+        table.set("monitor_label", monitor_label.to_string())?;
+        table.set("timestamp", chrono::Utc::now().timestamp())?;
+
+        let result: Value = on_post.call(table)?;
+        Ok(match result {
+            Value::Boolean(keep) => Some(PostDecision {
+                keep,
+                provider: None,
+                model: None,
+                prompt: None,
+                log_label: None,
+                text: None,
+                skip_ai: false,
+            }),
+            Value::Nil => Some(PostDecision {
+                keep: false,
+                provider: None,
+                model: None,
+                prompt: None,
+                log_label: None,
+                text: None,
+                skip_ai: false,
+            }),
+            Value::String(text) => Some(PostDecision {
+                keep: true,
+                provider: None,
+                model: None,
+                prompt: None,
+                log_label: None,
+                text: text.to_str().ok().map(|text| text.to_string()),
+                skip_ai: false,
+            }),
+            Value::Table(result) => Some(PostDecision {
+                keep: result.get("keep").unwrap_or(true),
+                provider: result.get("provider").ok(),
+                model: result.get("model").ok(),
+                prompt: result.get("prompt").ok(),
+                log_label: result.get("log_label").ok(),
+                text: result.get("text").ok(),
+                skip_ai: result.get("skip_ai").unwrap_or(false),
+            }),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,202 @@
+//! Lazy resolution of API-key references, so a raw secret never has to sit in
+//! the YAML a config viewer renders straight to the terminal. A config field
+//! is either a pasted literal (masked on screen as before) or a
+//! `scheme:payload` reference recognized by [`SecretRef::parse`] and resolved
+//! through a [`SecretSource`] only when a connection is about to be made.
+
+use std::env;
+
+use anyhow::{Context, Result};
+
+/// A parsed API-key field: a recognized `scheme:payload` reference, a bare
+/// env-var name, or a pasted literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    /// `$VAR` or a bare `VAR` — resolved from the process environment.
+    EnvVar(String),
+    /// `keyring:service/account` — resolved from the OS keyring.
+    Keyring { service: String, account: String },
+    /// `cmd:some shell command` — resolved by running the command and
+    /// trimming its stdout.
+    Command(String),
+    /// `file:path` — resolved by reading the file and trimming its contents.
+    File(String),
+    /// A pasted literal, used as-is.
+    Literal(String),
+}
+
+impl SecretRef {
+    /// Parse a config field's raw text into a secret reference. Never fails —
+    /// anything that isn't a recognized scheme or env-var shape falls back to
+    /// [`SecretRef::Literal`], matching today's paste-a-key behavior.
+    pub fn parse(input: &str) -> Self {
+        let trimmed = input.trim();
+
+        if let Some(var_name) = trimmed.strip_prefix('$') {
+            if is_env_var_name(var_name) {
+                return Self::EnvVar(var_name.to_string());
+            }
+            return Self::Literal(trimmed.to_string());
+        }
+        if is_env_var_name(trimmed) {
+            return Self::EnvVar(trimmed.to_string());
+        }
+        if let Some(rest) = trimmed.strip_prefix("keyring:") {
+            if let Some((service, account)) = rest.split_once('/') {
+                return Self::Keyring {
+                    service: service.to_string(),
+                    account: account.to_string(),
+                };
+            }
+        }
+        if let Some(rest) = trimmed.strip_prefix("cmd:") {
+            return Self::Command(rest.to_string());
+        }
+        if let Some(rest) = trimmed.strip_prefix("file:") {
+            return Self::File(rest.to_string());
+        }
+
+        Self::Literal(trimmed.to_string())
+    }
+
+    /// Whether this reference is safe to show un-masked on screen — an opaque
+    /// pointer to the secret rather than the secret itself.
+    pub fn is_opaque_reference(&self) -> bool {
+        !matches!(self, Self::Literal(_))
+    }
+
+    /// A short label for the reference kind, used in monitor detail views.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::EnvVar(_) => "env ref",
+            Self::Keyring { .. } => "keyring ref",
+            Self::Command(_) => "cmd ref",
+            Self::File(_) => "file ref",
+            Self::Literal(_) => "literal",
+        }
+    }
+
+    /// The text to display in place of the resolved secret: the reference
+    /// verbatim for opaque sources, or a run of asterisks for a literal.
+    pub fn display(&self, original: &str) -> String {
+        if self.is_opaque_reference() {
+            original.trim().to_string()
+        } else {
+            mask(original.trim())
+        }
+    }
+
+    /// Resolve the reference to its secret value. Only actually reads the
+    /// keyring, runs the command, or opens the file at connect time — never
+    /// while the user is just editing the form.
+    pub fn resolve(&self) -> Option<String> {
+        let resolved = match self {
+            Self::EnvVar(name) => env::var(name).ok(),
+            Self::Keyring { service, account } => KeyringSource { service, account }.fetch().ok(),
+            Self::Command(command) => CommandSource { command }.fetch().ok(),
+            Self::File(path) => FileSource { path }.fetch().ok(),
+            Self::Literal(value) => Some(value.clone()),
+        };
+        resolved
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Store `value` into the OS keyring under `service`/`account`, for the
+/// add/edit form's "move this key to the keyring" action. Returns the
+/// `keyring:service/account` reference to put in the field in its place.
+pub fn store_in_keyring(service: &str, account: &str, value: &str) -> Result<String> {
+    let entry = keyring::Entry::new(service, account)
+        .with_context(|| format!("failed to open keyring entry {service}/{account}"))?;
+    entry
+        .set_password(value)
+        .with_context(|| format!("failed to store keyring entry {service}/{account}"))?;
+    Ok(format!("keyring:{service}/{account}"))
+}
+
+fn mask(input: &str) -> String {
+    if input.is_empty() {
+        return String::new();
+    }
+    "*".repeat(input.chars().count())
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch == '_' || ch.is_ascii_uppercase() || ch.is_ascii_digit())
+}
+
+/// A place an API key's actual value can be fetched from.
+trait SecretSource {
+    fn fetch(&self) -> Result<String>;
+}
+
+struct KeyringSource<'a> {
+    service: &'a str,
+    account: &'a str,
+}
+
+impl SecretSource for KeyringSource<'_> {
+    fn fetch(&self) -> Result<String> {
+        let entry = keyring::Entry::new(self.service, self.account).with_context(|| {
+            format!(
+                "failed to open keyring entry {}/{}",
+                self.service, self.account
+            )
+        })?;
+        entry.get_password().with_context(|| {
+            format!(
+                "failed to read keyring entry {}/{}",
+                self.service, self.account
+            )
+        })
+    }
+}
+
+struct CommandSource<'a> {
+    command: &'a str,
+}
+
+impl SecretSource for CommandSource<'_> {
+    fn fetch(&self) -> Result<String> {
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(self.command)
+            .output()
+            .with_context(|| format!("failed to run secret command `{}`", self.command))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "secret command `{}` exited with {}",
+                self.command,
+                output.status
+            );
+        }
+        String::from_utf8(output.stdout).context("secret command output was not valid utf-8")
+    }
+}
+
+struct FileSource<'a> {
+    path: &'a str,
+}
+
+impl SecretSource for FileSource<'_> {
+    fn fetch(&self) -> Result<String> {
+        let expanded = expand_home(self.path);
+        std::fs::read_to_string(&expanded)
+            .with_context(|| format!("failed to read secret file {expanded}"))
+    }
+}
+
+/// Expand a leading `~/` to the user's home directory; paths without it are
+/// returned unchanged.
+fn expand_home(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = env::var_os("HOME") {
+            return format!("{}/{}", home.to_string_lossy(), rest);
+        }
+    }
+    path.to_string()
+}
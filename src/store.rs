@@ -0,0 +1,222 @@
+//! Embedded LMDB-backed store (via `heed`) that is the canonical home for
+//! monitor definitions and their AI configs, plus each monitor's rolling
+//! post-volume history. Unlike the flat JSON snapshot it replaces, writes are
+//! transactional, so a crash mid-edit can't leave the store half-written.
+//! YAML target files stay a convenience format: the picker imports a parsed
+//! file into this store, and monitors can be exported back out to YAML for
+//! sharing or version control.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use heed::{
+    Database, Env, EnvOpenOptions,
+    types::{Bytes, SerdeJson},
+};
+use uuid::Uuid;
+
+use crate::models::{Monitor, VolumeSeries};
+
+const MONITORS_DB_NAME: &str = "monitors";
+const VOLUME_DB_NAME: &str = "monitor_volume";
+const RSS_SEEN_DB_NAME: &str = "rss_seen";
+/// LMDB reserves this much address space up front; actual file size on disk
+/// only grows with what's actually written.
+const MAP_SIZE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Cheaply cloneable: `Env` and `Database` handles are thin references to the
+/// shared LMDB environment, so a clone can be handed to a spawned poll task
+/// (see `rss::poll_loop`) without the task needing a `&mut App`.
+#[derive(Clone)]
+pub struct MonitorStore {
+    env: Env,
+    monitors: Database<Bytes, SerdeJson<Monitor>>,
+    volume: Database<Bytes, SerdeJson<VolumeSeries>>,
+    rss_seen: Database<Bytes, SerdeJson<HashSet<String>>>,
+}
+
+impl std::fmt::Debug for MonitorStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MonitorStore").finish_non_exhaustive()
+    }
+}
+
+impl MonitorStore {
+    /// Open (creating if necessary) the LMDB environment at `dir`.
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create monitor store directory {}", dir.display()))?;
+
+        // Safety: we only ever open one environment per `dir` for the life of
+        // the process, satisfying heed's single-open-per-process requirement.
+        let env = unsafe { EnvOpenOptions::new().map_size(MAP_SIZE_BYTES).max_dbs(3).open(dir) }
+            .with_context(|| format!("failed to open monitor store at {}", dir.display()))?;
+
+        let mut wtxn = env
+            .write_txn()
+            .context("failed to start monitor store init transaction")?;
+        let monitors = env
+            .create_database(&mut wtxn, Some(MONITORS_DB_NAME))
+            .context("failed to open monitors database")?;
+        let volume = env
+            .create_database(&mut wtxn, Some(VOLUME_DB_NAME))
+            .context("failed to open monitor volume database")?;
+        let rss_seen = env
+            .create_database(&mut wtxn, Some(RSS_SEEN_DB_NAME))
+            .context("failed to open RSS seen-entry database")?;
+        wtxn.commit()
+            .context("failed to commit monitor store init transaction")?;
+
+        Ok(Self { env, monitors, volume, rss_seen })
+    }
+
+    /// All monitors currently in the store.
+    pub fn load_all(&self) -> Result<Vec<Monitor>> {
+        let rtxn = self
+            .env
+            .read_txn()
+            .context("failed to start monitor store read transaction")?;
+        self.monitors
+            .iter(&rtxn)
+            .context("failed to iterate monitor store")?
+            .map(|entry| {
+                entry
+                    .map(|(_, monitor)| monitor)
+                    .context("corrupt monitor store entry")
+            })
+            .collect()
+    }
+
+    /// Insert or overwrite `monitor`, keyed by its stable ID.
+    pub fn upsert(&self, monitor: &Monitor) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start monitor store write transaction")?;
+        self.monitors
+            .put(&mut wtxn, monitor.id.as_bytes(), monitor)
+            .context("failed to upsert monitor")?;
+        wtxn.commit()
+            .context("failed to commit monitor upsert")
+    }
+
+    /// Remove the monitor with `id`, if present.
+    pub fn remove(&self, id: Uuid) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start monitor store write transaction")?;
+        self.monitors
+            .delete(&mut wtxn, id.as_bytes())
+            .context("failed to remove monitor")?;
+        wtxn.commit()
+            .context("failed to commit monitor removal")
+    }
+
+    /// Replace the whole store contents with `monitors` in a single
+    /// transaction. Used to persist the in-memory list wholesale (e.g. on
+    /// quit) without tracking every incremental change.
+    pub fn replace_all(&self, monitors: &[Monitor]) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start monitor store write transaction")?;
+        self.monitors
+            .clear(&mut wtxn)
+            .context("failed to clear monitor store")?;
+        for monitor in monitors {
+            self.monitors
+                .put(&mut wtxn, monitor.id.as_bytes(), monitor)
+                .context("failed to write monitor")?;
+        }
+        wtxn.commit()
+            .context("failed to commit monitor store replace")
+    }
+
+    /// All persisted per-monitor volume series, keyed by monitor id.
+    pub fn load_all_volume(&self) -> Result<HashMap<Uuid, VolumeSeries>> {
+        let rtxn = self
+            .env
+            .read_txn()
+            .context("failed to start monitor volume read transaction")?;
+        self.volume
+            .iter(&rtxn)
+            .context("failed to iterate monitor volume store")?
+            .map(|entry| {
+                let (key, series) = entry.context("corrupt monitor volume entry")?;
+                let id = Uuid::from_slice(key).context("corrupt monitor volume key")?;
+                Ok((id, series))
+            })
+            .collect()
+    }
+
+    /// Insert or overwrite the volume series for `id`.
+    pub fn save_volume(&self, id: Uuid, series: &VolumeSeries) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start monitor volume write transaction")?;
+        self.volume
+            .put(&mut wtxn, id.as_bytes(), series)
+            .context("failed to upsert monitor volume")?;
+        wtxn.commit()
+            .context("failed to commit monitor volume upsert")
+    }
+
+    /// Remove the volume series for `id`, if present.
+    pub fn remove_volume(&self, id: Uuid) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start monitor volume write transaction")?;
+        self.volume
+            .delete(&mut wtxn, id.as_bytes())
+            .context("failed to remove monitor volume")?;
+        wtxn.commit()
+            .context("failed to commit monitor volume removal")
+    }
+
+    /// The set of RSS/Atom entry IDs already seen for monitor `id`, so a poll
+    /// loop restart doesn't re-emit everything in the feed.
+    pub fn load_rss_seen(&self, id: Uuid) -> Result<HashSet<String>> {
+        let rtxn = self
+            .env
+            .read_txn()
+            .context("failed to start RSS seen-entry read transaction")?;
+        Ok(self
+            .rss_seen
+            .get(&rtxn, id.as_bytes())
+            .context("failed to read RSS seen-entry set")?
+            .unwrap_or_default())
+    }
+
+    /// Overwrite the seen-entry set for RSS monitor `id`.
+    pub fn save_rss_seen(&self, id: Uuid, seen: &HashSet<String>) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start RSS seen-entry write transaction")?;
+        self.rss_seen
+            .put(&mut wtxn, id.as_bytes(), seen)
+            .context("failed to upsert RSS seen-entry set")?;
+        wtxn.commit()
+            .context("failed to commit RSS seen-entry upsert")
+    }
+
+    /// Remove the seen-entry set for `id`, if present.
+    pub fn remove_rss_seen(&self, id: Uuid) -> Result<()> {
+        let mut wtxn = self
+            .env
+            .write_txn()
+            .context("failed to start RSS seen-entry write transaction")?;
+        self.rss_seen
+            .delete(&mut wtxn, id.as_bytes())
+            .context("failed to remove RSS seen-entry set")?;
+        wtxn.commit()
+            .context("failed to commit RSS seen-entry removal")
+    }
+}
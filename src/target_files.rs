@@ -1,12 +1,17 @@
 use std::{
+    collections::HashMap,
     fs,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::models::MonitorKind;
+use crate::{
+    config::ForwarderConfig,
+    models::{Monitor, MonitorKind},
+};
 
 #[derive(Debug, Clone)]
 pub struct TargetFileMonitor {
@@ -19,6 +24,19 @@ pub struct TargetFileMonitor {
     pub ai_endpoint: Option<String>,
     pub ai_api_key: Option<String>,
     pub ai_prompt: Option<String>,
+    pub ai_temperature: Option<f32>,
+    pub ai_max_tokens: Option<u32>,
+    pub ai_top_p: Option<f32>,
+    pub ai_notify: bool,
+    pub ai_use_tools: bool,
+    pub notify: bool,
+    /// External command to run when a post matches this target, overriding
+    /// `hooks.on_post` for this monitor only.
+    pub on_match: Option<String>,
+    /// Webhook sinks overriding `AppConfig.forwarders` for this target only.
+    pub sinks: Option<Vec<ForwarderConfig>>,
+    /// Lua script overriding `AppConfig.lua_script` for this target only.
+    pub script: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -26,7 +44,49 @@ pub struct TargetFileEntry {
     pub file_name: String,
     pub path: PathBuf,
     pub raw: String,
-    pub parsed: Result<TargetFileMonitor, String>,
+    pub parsed: Result<TargetFileMonitor, TargetFileError>,
+    /// Last-modified time at load, used to key the syntax-highlight cache so
+    /// it's invalidated if the file changes on disk.
+    pub mtime: Option<SystemTime>,
+}
+
+/// One annotated location in the raw source an error relates to: a 1-indexed
+/// `(line, column)` plus how many characters the underlined token spans.
+/// `primary` spans get a `^^^` underline, secondary ones (e.g. the earlier
+/// definition in a duplicate-key error) get a muted `---` underline. Rendered
+/// by `ui::preview_target_file`.
+#[derive(Debug, Clone)]
+pub struct ErrorSpan {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+    pub primary: bool,
+    pub label: Option<String>,
+}
+
+/// A failed parse of a target file, carrying enough location info to render
+/// an in-context caret annotation under the offending source line when one is
+/// available (not every validation failure has a location — e.g. "target
+/// cannot be empty" doesn't point anywhere in particular).
+#[derive(Debug, Clone)]
+pub struct TargetFileError {
+    pub message: String,
+    pub spans: Vec<ErrorSpan>,
+}
+
+impl TargetFileError {
+    fn plain(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            spans: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for TargetFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,6 +111,24 @@ struct RawTargetFile {
     ai_api_key: Option<String>,
     #[serde(default)]
     ai_prompt: Option<String>,
+    #[serde(default)]
+    ai_temperature: Option<f32>,
+    #[serde(default)]
+    ai_max_tokens: Option<u32>,
+    #[serde(default)]
+    ai_top_p: Option<f32>,
+    #[serde(default)]
+    ai_notify: Option<bool>,
+    #[serde(default)]
+    ai_use_tools: Option<bool>,
+    #[serde(default)]
+    notify: Option<bool>,
+    #[serde(default)]
+    on_match: Option<String>,
+    #[serde(default)]
+    sinks: Option<Vec<ForwarderConfig>>,
+    #[serde(default)]
+    script: Option<PathBuf>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -67,6 +145,16 @@ struct RawAiConfig {
     api_key: Option<String>,
     #[serde(default)]
     prompt: Option<String>,
+    #[serde(default)]
+    temperature: Option<f32>,
+    #[serde(default)]
+    max_tokens: Option<u32>,
+    #[serde(default)]
+    top_p: Option<f32>,
+    #[serde(default)]
+    notify: Option<bool>,
+    #[serde(default)]
+    use_tools: Option<bool>,
 }
 
 pub fn load_target_file_entries(dir: &Path) -> Result<Vec<TargetFileEntry>> {
@@ -93,14 +181,19 @@ pub fn load_target_file_entries(dir: &Path) -> Result<Vec<TargetFileEntry>> {
             .map(|name| name.to_string())
             .unwrap_or_else(|| path.display().to_string());
 
+        let mtime = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
         match fs::read_to_string(&path) {
             Ok(raw) => {
-                let parsed = parse_target_file(&raw).map_err(|error| error.to_string());
+                let parsed = parse_target_file(&raw);
                 entries.push(TargetFileEntry {
                     file_name,
                     path,
                     raw,
                     parsed,
+                    mtime,
                 });
             }
             Err(error) => {
@@ -108,7 +201,10 @@ pub fn load_target_file_entries(dir: &Path) -> Result<Vec<TargetFileEntry>> {
                     file_name,
                     path,
                     raw: String::new(),
-                    parsed: Err(format!("failed to read file: {error}")),
+                    parsed: Err(TargetFileError::plain(format!(
+                        "failed to read file: {error}"
+                    ))),
+                    mtime,
                 });
             }
         }
@@ -118,14 +214,18 @@ pub fn load_target_file_entries(dir: &Path) -> Result<Vec<TargetFileEntry>> {
     Ok(entries)
 }
 
-fn parse_target_file(raw: &str) -> Result<TargetFileMonitor> {
+fn parse_target_file(raw: &str) -> Result<TargetFileMonitor, TargetFileError> {
+    if let Some((key, first_line, duplicate_line)) = find_duplicate_top_level_key(raw) {
+        return Err(duplicate_key_error(&key, first_line, duplicate_line));
+    }
+
     let parsed: RawTargetFile =
-        serde_yaml::from_str(raw).context("invalid YAML format for target config")?;
+        serde_yaml::from_str(raw).map_err(|error| yaml_error_to_target_file_error(&error, raw))?;
 
-    let kind = parse_kind(&parsed.kind)?;
+    let kind = parse_kind(&parsed.kind).map_err(TargetFileError::plain)?;
     let target = parsed.target.trim().to_string();
     if target.is_empty() {
-        anyhow::bail!("target cannot be empty");
+        return Err(TargetFileError::plain("target cannot be empty"));
     }
 
     let ai = parsed.ai.unwrap_or_default();
@@ -134,11 +234,19 @@ fn parse_target_file(raw: &str) -> Result<TargetFileMonitor> {
     let ai_endpoint = clean_opt(parsed.ai_endpoint.or(ai.endpoint));
     let ai_api_key = clean_opt(parsed.ai_api_key.or(ai.api_key));
     let ai_prompt = clean_opt(parsed.ai_prompt.or(ai.prompt));
+    let ai_temperature = parsed.ai_temperature.or(ai.temperature);
+    let ai_max_tokens = parsed.ai_max_tokens.or(ai.max_tokens);
+    let ai_top_p = parsed.ai_top_p.or(ai.top_p);
+    let ai_notify = parsed.ai_notify.or(ai.notify).unwrap_or(false);
+    let ai_use_tools = parsed.ai_use_tools.or(ai.use_tools).unwrap_or(false);
     let any_ai_value = ai_provider.is_some()
         || ai_model.is_some()
         || ai_endpoint.is_some()
         || ai_api_key.is_some()
-        || ai_prompt.is_some();
+        || ai_prompt.is_some()
+        || ai_temperature.is_some()
+        || ai_max_tokens.is_some()
+        || ai_top_p.is_some();
     let ai_enabled = parsed.ai_enabled.or(ai.enabled).unwrap_or(any_ai_value);
 
     Ok(TargetFileMonitor {
@@ -151,15 +259,231 @@ fn parse_target_file(raw: &str) -> Result<TargetFileMonitor> {
         ai_endpoint,
         ai_api_key,
         ai_prompt,
+        ai_temperature,
+        ai_max_tokens,
+        ai_top_p,
+        ai_notify,
+        ai_use_tools,
+        notify: parsed.notify.unwrap_or(false),
+        on_match: clean_opt(parsed.on_match),
+        sinks: parsed.sinks.filter(|sinks| !sinks.is_empty()),
+        script: parsed.script,
     })
 }
 
-fn parse_kind(kind: &str) -> Result<MonitorKind> {
+/// YAML shape written by [`export_monitor_to_yaml`]; the mirror image of
+/// [`RawTargetFile`] for a store-backed monitor, rather than a hand-authored
+/// target file.
+#[derive(Debug, Serialize)]
+struct ExportTargetFile {
+    label: String,
+    kind: String,
+    target: String,
+    notify: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on_match: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sinks: Option<Vec<ForwarderConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    script: Option<PathBuf>,
+    ai: ExportAiConfig,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ExportAiConfig {
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    notify: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    use_tools: bool,
+}
+
+/// Write `monitor` out as a YAML target file in `dir`, the symmetric
+/// counterpart to importing one through the target-file picker. The store
+/// stays the source of truth; this is just a convenience snapshot.
+pub fn export_monitor_to_yaml(monitor: &Monitor, dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let kind = match monitor.kind {
+        MonitorKind::Account => "account",
+        MonitorKind::Phrase => "phrase",
+        MonitorKind::Rss => "rss",
+    };
+
+    let export = ExportTargetFile {
+        label: monitor.label.clone(),
+        kind: kind.to_string(),
+        target: monitor.input_value.clone(),
+        notify: monitor.notify,
+        on_match: monitor.on_match.clone(),
+        sinks: monitor.sinks.clone(),
+        script: monitor.script.clone(),
+        ai: ExportAiConfig {
+            enabled: monitor.analysis.enabled,
+            provider: clean_opt(Some(monitor.analysis.provider.clone())),
+            model: clean_opt(Some(monitor.analysis.model.clone())),
+            endpoint: clean_opt(Some(monitor.analysis.endpoint.clone())),
+            api_key: clean_opt(Some(monitor.analysis.api_key.clone())),
+            prompt: clean_opt(Some(monitor.analysis.prompt.clone())),
+            temperature: monitor.analysis.temperature,
+            max_tokens: monitor.analysis.max_tokens,
+            top_p: monitor.analysis.top_p,
+            notify: monitor.analysis.notify,
+            use_tools: monitor.analysis.use_tools,
+        },
+    };
+
+    let rendered = serde_yaml::to_string(&export).context("failed to render monitor as YAML")?;
+    let path = unique_export_path(dir, &monitor.label);
+    fs::write(&path, rendered).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn unique_export_path(dir: &Path, label: &str) -> PathBuf {
+    let slug = slugify(label);
+    let mut candidate = dir.join(format!("{slug}.yaml"));
+    let mut suffix = 2;
+    while candidate.exists() {
+        candidate = dir.join(format!("{slug}-{suffix}.yaml"));
+        suffix += 1;
+    }
+    candidate
+}
+
+fn slugify(label: &str) -> String {
+    let mut slug: String = label
+        .trim()
+        .to_ascii_lowercase()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect();
+    while slug.contains("--") {
+        slug = slug.replace("--", "-");
+    }
+    let trimmed = slug.trim_matches('-');
+    if trimmed.is_empty() {
+        "monitor".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn parse_kind(kind: &str) -> Result<MonitorKind, String> {
     match kind.trim().to_ascii_lowercase().as_str() {
         "account" | "accounts" | "acct" => Ok(MonitorKind::Account),
         "phrase" | "phrases" | "keyword" | "keywords" => Ok(MonitorKind::Phrase),
-        _ => anyhow::bail!("kind must be 'account' or 'phrase'"),
+        "rss" | "atom" | "feed" => Ok(MonitorKind::Rss),
+        _ => Err("kind must be 'account', 'phrase', or 'rss'".to_string()),
+    }
+}
+
+/// Scan for a top-level (column-0, unindented) `key:` line repeated later in
+/// the file, returning `(key, first_line, duplicate_line)` (1-indexed). Keys
+/// nested under `ai:`/`sinks:` are indented and not considered, matching the
+/// shape of a real target file.
+fn find_duplicate_top_level_key(raw: &str) -> Option<(String, usize, usize)> {
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+    for (index, line) in raw.lines().enumerate() {
+        let line_number = index + 1;
+        if line.starts_with(char::is_whitespace) || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let Some(colon) = line.find(':') else {
+            continue;
+        };
+        let key = line[..colon].trim();
+        if key.is_empty() || !key.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_') {
+            continue;
+        }
+
+        if let Some(&first_line) = first_seen.get(key) {
+            return Some((key.to_string(), first_line, line_number));
+        }
+        first_seen.insert(key, line_number);
     }
+    None
+}
+
+/// Build the two-span error for a duplicate top-level key: a primary `^^^`
+/// at the repeated definition and a muted secondary `---` at the first one.
+fn duplicate_key_error(key: &str, first_line: usize, duplicate_line: usize) -> TargetFileError {
+    let len = key.len();
+    TargetFileError {
+        message: format!("duplicate key '{key}' (first set at line {first_line})"),
+        spans: vec![
+            ErrorSpan {
+                line: duplicate_line,
+                column: 1,
+                len,
+                primary: true,
+                label: Some(format!("duplicate '{key}'")),
+            },
+            ErrorSpan {
+                line: first_line,
+                column: 1,
+                len,
+                primary: false,
+                label: Some("first defined here".to_string()),
+            },
+        ],
+    }
+}
+
+/// Turn a `serde_yaml` parse failure into a [`TargetFileError`], pulling the
+/// `(line, column)` out of the error when available and estimating how many
+/// characters of the offending line to underline.
+fn yaml_error_to_target_file_error(error: &serde_yaml::Error, raw: &str) -> TargetFileError {
+    let message = format!("invalid YAML format for target config: {error}");
+    let Some(location) = error.location() else {
+        return TargetFileError::plain(message);
+    };
+
+    let line = location.line();
+    let column = location.column();
+    let len = raw
+        .lines()
+        .nth(line.saturating_sub(1))
+        .map(|text| token_len_at(text, column.saturating_sub(1)))
+        .unwrap_or(1);
+
+    TargetFileError {
+        message,
+        spans: vec![ErrorSpan {
+            line,
+            column,
+            len,
+            primary: true,
+            label: Some(error.to_string()),
+        }],
+    }
+}
+
+/// Length of the run of non-whitespace/non-delimiter characters starting at
+/// `column0` (0-indexed) in `line`, used to size the `^^^` underline.
+fn token_len_at(line: &str, column0: usize) -> usize {
+    line.get(column0.min(line.len())..)
+        .map(|rest| {
+            rest.chars()
+                .take_while(|ch| !ch.is_whitespace() && *ch != ':' && *ch != ',')
+                .count()
+                .max(1)
+        })
+        .unwrap_or(1)
 }
 
 fn clean_opt(value: Option<String>) -> Option<String> {
@@ -0,0 +1,399 @@
+use std::{collections::BTreeMap, env};
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// A color that can be written in the config either as a named terminal color
+/// (`"cyan"`, `"light-blue"`, `"dark-gray"`), a 24-bit hex string (`"#1e1e2e"`),
+/// or a raw 0-255 palette index (`"240"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeColor(pub Color);
+
+impl ThemeColor {
+    fn parse(raw: &str) -> Option<Color> {
+        let value = raw.trim();
+        if value.is_empty() {
+            return None;
+        }
+
+        if let Some(hex) = value.strip_prefix('#') {
+            if hex.len() == 6 {
+                let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+                let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+                let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+                return Some(Color::Rgb(r, g, b));
+            }
+            return None;
+        }
+
+        if let Ok(index) = value.parse::<u8>() {
+            return Some(Color::Indexed(index));
+        }
+
+        let normalized = value.replace(['-', '_', ' '], "").to_ascii_lowercase();
+        let color = match normalized.as_str() {
+            "reset" | "default" => Color::Reset,
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "gray" | "grey" => Color::Gray,
+            "darkgray" | "darkgrey" => Color::DarkGray,
+            "lightred" => Color::LightRed,
+            "lightgreen" => Color::LightGreen,
+            "lightyellow" => Color::LightYellow,
+            "lightblue" => Color::LightBlue,
+            "lightmagenta" => Color::LightMagenta,
+            "lightcyan" => Color::LightCyan,
+            "white" => Color::White,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    fn name(color: Color) -> String {
+        match color {
+            Color::Reset => "default".to_string(),
+            Color::Black => "black".to_string(),
+            Color::Red => "red".to_string(),
+            Color::Green => "green".to_string(),
+            Color::Yellow => "yellow".to_string(),
+            Color::Blue => "blue".to_string(),
+            Color::Magenta => "magenta".to_string(),
+            Color::Cyan => "cyan".to_string(),
+            Color::Gray => "gray".to_string(),
+            Color::DarkGray => "dark-gray".to_string(),
+            Color::LightRed => "light-red".to_string(),
+            Color::LightGreen => "light-green".to_string(),
+            Color::LightYellow => "light-yellow".to_string(),
+            Color::LightBlue => "light-blue".to_string(),
+            Color::LightMagenta => "light-magenta".to_string(),
+            Color::LightCyan => "light-cyan".to_string(),
+            Color::White => "white".to_string(),
+            Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+            Color::Indexed(index) => index.to_string(),
+        }
+    }
+}
+
+impl Serialize for ThemeColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&Self::name(self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Self::parse(&raw)
+            .map(ThemeColor)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown color `{raw}`")))
+    }
+}
+
+/// Text modifiers that can be added to or subtracted from a style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeModifier {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    Reversed,
+    CrossedOut,
+}
+
+impl ThemeModifier {
+    fn flag(self) -> Modifier {
+        match self {
+            Self::Bold => Modifier::BOLD,
+            Self::Dim => Modifier::DIM,
+            Self::Italic => Modifier::ITALIC,
+            Self::Underlined => Modifier::UNDERLINED,
+            Self::Reversed => Modifier::REVERSED,
+            Self::CrossedOut => Modifier::CROSSED_OUT,
+        }
+    }
+}
+
+/// A serde-deserializable style mirroring [`ratatui::style::Style`]: optional
+/// foreground/background colors plus modifiers to add or remove. Any field left
+/// unset leaves the corresponding property untouched when merged onto a base.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeStyle {
+    pub fg: Option<ThemeColor>,
+    pub bg: Option<ThemeColor>,
+    pub add_modifiers: Vec<ThemeModifier>,
+    pub sub_modifiers: Vec<ThemeModifier>,
+}
+
+impl ThemeStyle {
+    fn to_style(&self) -> Style {
+        let mut style = Style::default();
+        if let Some(fg) = self.fg {
+            style = style.fg(fg.0);
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg.0);
+        }
+        for modifier in &self.add_modifiers {
+            style = style.add_modifier(modifier.flag());
+        }
+        for modifier in &self.sub_modifiers {
+            style = style.remove_modifier(modifier.flag());
+        }
+        style
+    }
+}
+
+/// How a theme is selected in the config: a built-in palette name plus optional
+/// per-role overrides keyed by the field names of [`Theme`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// Built-in palette to start from: `default`, `high-contrast`, or `mono`.
+    pub palette: Option<String>,
+    /// Overrides applied on top of the palette, keyed by role name.
+    pub styles: BTreeMap<String, ThemeStyle>,
+}
+
+macro_rules! theme_roles {
+    ($($field:ident),+ $(,)?) => {
+        /// Fully resolved styles pulled by every `render_*` function. Built once
+        /// at startup from a [`ThemeConfig`]; honors `NO_COLOR` by collapsing
+        /// every role to the terminal default.
+        #[derive(Debug, Clone)]
+        pub struct Theme {
+            $(pub $field: Style,)+
+        }
+
+        impl Theme {
+            fn set(&mut self, key: &str, style: Style) -> bool {
+                match key {
+                    $(stringify!($field) => {
+                        self.$field = self.$field.patch(style);
+                        true
+                    })+
+                    _ => false,
+                }
+            }
+
+            fn collapse(&mut self) {
+                $(self.$field = Style::default();)+
+            }
+        }
+    };
+}
+
+theme_roles! {
+    header_title,
+    clock,
+    border,
+    border_focused,
+    modal_border,
+    stream_connected,
+    stream_disconnected,
+    status_active,
+    status_inactive,
+    selection,
+    feed_post,
+    feed_analysis,
+    feed_info,
+    feed_error,
+    feed_unseen,
+    feed_seen,
+    feed_zebra,
+    hint_key,
+    field_selected,
+    field_hint,
+    muted,
+    picker_valid,
+    picker_invalid,
+    filter_match,
+    yaml_key,
+    yaml_punctuation,
+    yaml_bool,
+    yaml_null,
+    yaml_number,
+    yaml_string,
+    yaml_anchor,
+    yaml_plain,
+    yaml_list_marker,
+    yaml_comment,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let bold = Modifier::BOLD;
+        Self {
+            header_title: Style::default().fg(Color::Cyan).add_modifier(bold),
+            clock: Style::default().fg(Color::Cyan),
+            border: Style::default().fg(Color::DarkGray),
+            border_focused: Style::default().fg(Color::Cyan),
+            modal_border: Style::default().fg(Color::Cyan),
+            stream_connected: Style::default().fg(Color::Green),
+            stream_disconnected: Style::default().fg(Color::Red),
+            status_active: Style::default().fg(Color::Green),
+            status_inactive: Style::default().fg(Color::Red),
+            selection: Style::default().fg(Color::White).bg(Color::Blue),
+            feed_post: Style::default().fg(Color::White),
+            feed_analysis: Style::default().fg(Color::LightBlue),
+            feed_info: Style::default().fg(Color::Gray),
+            feed_error: Style::default().fg(Color::LightRed),
+            feed_unseen: Style::default().fg(Color::Yellow).add_modifier(bold),
+            feed_seen: Style::default().add_modifier(Modifier::DIM),
+            feed_zebra: Style::default().bg(Color::Indexed(236)),
+            hint_key: Style::default().fg(Color::Green),
+            field_selected: Style::default().fg(Color::Yellow),
+            field_hint: Style::default().fg(Color::DarkGray),
+            muted: Style::default().fg(Color::DarkGray),
+            picker_valid: Style::default().fg(Color::Green),
+            picker_invalid: Style::default().fg(Color::Red),
+            filter_match: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            yaml_key: Style::default().fg(Color::Cyan).add_modifier(bold),
+            yaml_punctuation: Style::default().fg(Color::DarkGray),
+            yaml_bool: Style::default().fg(Color::Green),
+            yaml_null: Style::default().fg(Color::Gray),
+            yaml_number: Style::default().fg(Color::LightMagenta),
+            yaml_string: Style::default().fg(Color::Yellow),
+            yaml_anchor: Style::default().fg(Color::LightCyan),
+            yaml_plain: Style::default().fg(Color::White),
+            yaml_list_marker: Style::default().fg(Color::LightMagenta).add_modifier(bold),
+            yaml_comment: Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        }
+    }
+}
+
+impl Theme {
+    /// Resolve a theme from its config, applying overrides and `NO_COLOR`.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = config
+            .palette
+            .as_deref()
+            .and_then(Self::named)
+            .unwrap_or_default();
+
+        for (key, style) in &config.styles {
+            theme.set(key, style.to_style());
+        }
+
+        if no_color() {
+            theme.collapse();
+        }
+
+        theme
+    }
+
+    /// Look up a built-in palette by name. Returns `None` for unknown names so
+    /// the caller falls back to the default palette.
+    pub fn named(name: &str) -> Option<Self> {
+        match name.replace(['-', '_', ' '], "").to_ascii_lowercase().as_str() {
+            "default" | "dark" => Some(Self::default()),
+            "highcontrast" => Some(Self::high_contrast()),
+            "mono" | "monochrome" => Some(Self::mono()),
+            _ => None,
+        }
+    }
+
+    /// A color-blind-friendly palette leaning on blue/yellow contrast and bold.
+    fn high_contrast() -> Self {
+        let bold = Modifier::BOLD;
+        Self {
+            header_title: Style::default().fg(Color::White).add_modifier(bold),
+            clock: Style::default().fg(Color::White),
+            border: Style::default().fg(Color::Gray),
+            border_focused: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            modal_border: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            stream_connected: Style::default().fg(Color::LightBlue).add_modifier(bold),
+            stream_disconnected: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            status_active: Style::default().fg(Color::LightBlue).add_modifier(bold),
+            status_inactive: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            selection: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(bold),
+            feed_post: Style::default().fg(Color::White),
+            feed_analysis: Style::default().fg(Color::LightBlue),
+            feed_info: Style::default().fg(Color::Gray),
+            feed_error: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            feed_unseen: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            feed_seen: Style::default().add_modifier(Modifier::DIM),
+            feed_zebra: Style::default().bg(Color::Indexed(236)),
+            hint_key: Style::default().fg(Color::LightBlue).add_modifier(bold),
+            field_selected: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            field_hint: Style::default().fg(Color::Gray),
+            muted: Style::default().fg(Color::Gray),
+            picker_valid: Style::default().fg(Color::LightBlue).add_modifier(bold),
+            picker_invalid: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            filter_match: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            yaml_key: Style::default().fg(Color::LightBlue).add_modifier(bold),
+            yaml_punctuation: Style::default().fg(Color::Gray),
+            yaml_bool: Style::default().fg(Color::LightBlue),
+            yaml_null: Style::default().fg(Color::Gray),
+            yaml_number: Style::default().fg(Color::LightYellow),
+            yaml_string: Style::default().fg(Color::White),
+            yaml_anchor: Style::default().fg(Color::LightCyan),
+            yaml_plain: Style::default().fg(Color::White),
+            yaml_list_marker: Style::default().fg(Color::LightYellow).add_modifier(bold),
+            yaml_comment: Style::default().fg(Color::Gray).add_modifier(bold),
+        }
+    }
+
+    /// A no-hue palette that distinguishes state purely through brightness and
+    /// modifiers, for terminals where colors are unavailable or undesirable.
+    fn mono() -> Self {
+        let bold = Modifier::BOLD;
+        let dim = Modifier::DIM;
+        Self {
+            header_title: Style::default().add_modifier(bold),
+            clock: Style::default(),
+            border: Style::default().add_modifier(dim),
+            border_focused: Style::default().add_modifier(bold),
+            modal_border: Style::default().add_modifier(bold),
+            stream_connected: Style::default().add_modifier(bold),
+            stream_disconnected: Style::default().add_modifier(dim),
+            status_active: Style::default().add_modifier(bold),
+            status_inactive: Style::default().add_modifier(dim),
+            selection: Style::default().add_modifier(Modifier::REVERSED),
+            feed_post: Style::default(),
+            feed_analysis: Style::default().add_modifier(bold),
+            feed_info: Style::default().add_modifier(dim),
+            feed_error: Style::default().add_modifier(bold),
+            feed_unseen: Style::default().add_modifier(bold),
+            feed_seen: Style::default().add_modifier(dim),
+            feed_zebra: Style::default(),
+            hint_key: Style::default().add_modifier(bold),
+            field_selected: Style::default().add_modifier(bold),
+            field_hint: Style::default().add_modifier(dim),
+            muted: Style::default().add_modifier(dim),
+            picker_valid: Style::default().add_modifier(bold),
+            picker_invalid: Style::default().add_modifier(dim),
+            filter_match: Style::default().add_modifier(bold),
+            yaml_key: Style::default().add_modifier(bold),
+            yaml_punctuation: Style::default().add_modifier(dim),
+            yaml_bool: Style::default().add_modifier(bold),
+            yaml_null: Style::default().add_modifier(dim),
+            yaml_number: Style::default(),
+            yaml_string: Style::default(),
+            yaml_anchor: Style::default().add_modifier(bold),
+            yaml_plain: Style::default(),
+            yaml_list_marker: Style::default().add_modifier(bold),
+            yaml_comment: Style::default().add_modifier(dim),
+        }
+    }
+}
+
+/// Whether `NO_COLOR` is set to a non-empty value (see <https://no-color.org>).
+fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
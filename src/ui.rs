@@ -1,18 +1,25 @@
+use std::collections::HashSet;
+
 use chrono::Local;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use crate::{
-    app::{AddMonitorForm, App, FocusPane, MonitorFormMode, TargetFilePicker},
+    app::{AddMonitorForm, App, FocusPane, MonitorFormMode, PreviewMode, PromptPicker, TargetFilePicker},
+    config::Action,
+    fuzzy::FuzzyMatch,
     models::{FeedKind, MonitorKind},
+    theme::Theme,
 };
 
-pub fn render(frame: &mut Frame<'_>, app: &App) {
+pub fn render(frame: &mut Frame<'_>, app: &mut App) {
+    app.tick_monitor_volumes();
+
     let root = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -31,12 +38,29 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
     if let Some(form) = &app.add_form {
         render_add_modal(frame, app, form);
     }
-    if let Some(picker) = &app.target_file_picker {
-        render_target_file_picker(frame, picker);
+    if app.target_file_picker.is_some() {
+        let highlighted = app.target_file_preview_highlight().map(|lines| lines.to_vec());
+        if let Some(picker) = &app.target_file_picker {
+            render_target_file_picker(frame, app, picker, highlighted.as_deref());
+        }
+    }
+    if app.filter_overlay.is_some() {
+        // Clone the overlay's scored matches out so the mutable borrow used to
+        // (re)compute them ends before we read `app` immutably to render.
+        let matches = app.filter_overlay_matches().to_vec();
+        let overlay = app.filter_overlay.as_ref().expect("checked above");
+        let pane = overlay.pane;
+        let query = overlay.query.clone();
+        let selected = overlay.selected;
+        render_filter_overlay(frame, app, pane, &query, selected, &matches);
+    }
+    if let Some(picker) = &app.prompt_picker {
+        render_prompt_picker(frame, &app.theme, picker);
     }
 }
 
 fn render_header(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let theme = &app.theme;
     let stream_connected = app.stream_connected();
     let now = Local::now().format("%H:%M:%S").to_string();
     let stream_status_text = if stream_connected {
@@ -45,30 +69,33 @@ fn render_header(frame: &mut Frame<'_>, app: &App, area: Rect) {
         "Stream: disconnected"
     };
     let stream_status_style = if stream_connected {
-        Style::default().fg(Color::Green)
+        theme.stream_connected
     } else {
-        Style::default().fg(Color::Red)
+        theme.stream_disconnected
     };
 
-    let title = Line::from(vec![
-        Span::styled(
-            "𝕏 Monitor",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
+    let unseen = app.unseen_feed_count();
+
+    let mut title_spans = vec![
+        Span::styled("𝕏 Monitor", theme.header_title),
         Span::raw("  |  "),
         Span::raw(format!("Monitors: {}", app.monitors.len())),
         Span::raw("  |  "),
         Span::styled(stream_status_text, stream_status_style),
-        Span::raw("  |  "),
-        Span::raw(&app.status),
-    ]);
+    ];
+    if unseen > 0 {
+        title_spans.push(Span::raw("  |  "));
+        title_spans.push(Span::styled(format!("Unseen: {unseen}"), theme.feed_unseen));
+    }
+    title_spans.push(Span::raw("  |  "));
+    title_spans.push(Span::raw(&app.status));
+
+    let title = Line::from(title_spans);
 
     let block = Block::default()
         .title("Home")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(theme.border);
     frame.render_widget(block, area);
 
     let inner = area.inner(Margin {
@@ -89,7 +116,7 @@ fn render_header(frame: &mut Frame<'_>, app: &App, area: Rect) {
 
     let right = Paragraph::new(now)
         .alignment(Alignment::Right)
-        .style(Style::default().fg(Color::Cyan));
+        .style(theme.clock);
     frame.render_widget(right, cols[1]);
 }
 
@@ -104,6 +131,7 @@ fn render_body(frame: &mut Frame<'_>, app: &App, area: Rect) {
 }
 
 fn render_monitors(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let theme = &app.theme;
     let items = if app.monitors.is_empty() {
         vec![ListItem::new(Line::from(
             "No monitors yet. Press 'a' to add one.",
@@ -118,6 +146,7 @@ fn render_monitors(frame: &mut Frame<'_>, app: &App, area: Rect) {
                 let kind = match monitor.kind {
                     MonitorKind::Account => "acct",
                     MonitorKind::Phrase => "phrase",
+                    MonitorKind::Rss => "rss",
                 };
                 let ai = if monitor.analysis.enabled {
                     format!("AI:{}", monitor.analysis.provider)
@@ -126,18 +155,22 @@ fn render_monitors(frame: &mut Frame<'_>, app: &App, area: Rect) {
                 };
 
                 let (status, mut status_style) = if !monitor.enabled {
-                    ("off", Style::default().fg(Color::Red))
+                    ("off", theme.status_inactive)
                 } else if active {
-                    ("active", Style::default().fg(Color::Green))
+                    ("active", theme.status_active)
                 } else {
-                    ("inactive", Style::default().fg(Color::Red))
+                    ("inactive", theme.status_inactive)
                 };
                 if selected {
-                    status_style = status_style.bg(Color::Blue).add_modifier(Modifier::BOLD);
+                    if let Some(bg) = theme.selection.bg {
+                        status_style = status_style.bg(bg);
+                    }
+                    status_style = status_style
+                        .add_modifier(theme.selection.add_modifier | Modifier::BOLD);
                 }
 
                 let info_style = if selected {
-                    Style::default().fg(Color::White).bg(Color::Blue)
+                    theme.selection
                 } else {
                     Style::default()
                 };
@@ -146,9 +179,11 @@ fn render_monitors(frame: &mut Frame<'_>, app: &App, area: Rect) {
                     Span::styled(format!("● {status}"), status_style),
                     Span::styled(" ", info_style),
                     Span::styled(format!("{} [{}] {}", monitor.label, kind, ai), info_style),
+                    Span::raw(" "),
+                    Span::styled(volume_sparkline(app.monitor_volume(monitor.id)), theme.feed_info),
                 ]));
                 if selected {
-                    item = item.style(Style::default().bg(Color::Blue));
+                    item = item.style(theme.selection);
                 }
                 item
             })
@@ -172,9 +207,9 @@ fn render_monitors(frame: &mut Frame<'_>, app: &App, area: Rect) {
                 .title(title)
                 .borders(Borders::ALL)
                 .border_style(if app.focus == FocusPane::Monitors {
-                    Style::default().fg(Color::Cyan)
+                    theme.border_focused
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    theme.border
                 }),
         )
         .highlight_symbol("» ");
@@ -182,52 +217,114 @@ fn render_monitors(frame: &mut Frame<'_>, app: &App, area: Rect) {
     frame.render_stateful_widget(list, area, &mut list_state);
 }
 
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// How many of the most recent buckets to show inline next to a monitor.
+const SPARK_WIDTH: usize = 12;
+
+/// Render the tail of a monitor's volume series as a compact block-character
+/// sparkline, scaled to the max count within the visible window.
+fn volume_sparkline(counts: &[u16]) -> String {
+    let recent = &counts[counts.len().saturating_sub(SPARK_WIDTH)..];
+    let max = recent.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return "·".repeat(recent.len());
+    }
+
+    recent
+        .iter()
+        .map(|&count| {
+            let level = (count as f32 / max as f32 * (SPARK_CHARS.len() - 1) as f32).round() as usize;
+            SPARK_CHARS[level.min(SPARK_CHARS.len() - 1)]
+        })
+        .collect()
+}
+
 fn render_footer(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let theme = &app.theme;
     let hints = if app.target_file_picker.is_some() {
         Line::from(vec![
-            Span::styled("Up/Down", Style::default().fg(Color::Green)),
+            Span::styled("Up/Down", theme.hint_key),
             Span::raw(" choose file  "),
-            Span::styled("Enter", Style::default().fg(Color::Green)),
+            Span::styled("Ctrl+t", theme.hint_key),
+            Span::raw(" cycle preview  "),
+            Span::styled("Enter", theme.hint_key),
             Span::raw(" connect from file  "),
-            Span::styled("q", Style::default().fg(Color::Green)),
+            Span::styled("q", theme.hint_key),
             Span::raw(" close picker"),
         ])
+    } else if app.filter_overlay.is_some() {
+        Line::from(vec![
+            Span::styled("type", theme.hint_key),
+            Span::raw(" filter  "),
+            Span::styled("Up/Down", theme.hint_key),
+            Span::raw(" choose  "),
+            Span::styled("Enter", theme.hint_key),
+            Span::raw(" jump to selection  "),
+            Span::styled("Esc", theme.hint_key),
+            Span::raw(" close"),
+        ])
+    } else if app.prompt_picker.is_some() {
+        Line::from(vec![
+            Span::styled("Up/Down", theme.hint_key),
+            Span::raw(" choose prompt  "),
+            Span::styled("Enter", theme.hint_key),
+            Span::raw(" insert  "),
+            Span::styled("s", theme.hint_key),
+            Span::raw(" save current prompt as...  "),
+            Span::styled("q", theme.hint_key),
+            Span::raw(" close"),
+        ])
     } else if app.add_form.is_some() {
         Line::from(vec![
-            Span::styled("Up/Down", Style::default().fg(Color::Green)),
+            Span::styled("Up/Down", theme.hint_key),
             Span::raw(" field  "),
-            Span::styled("Left/Right", Style::default().fg(Color::Green)),
+            Span::styled("Left/Right", theme.hint_key),
             Span::raw(" toggle/cycle  "),
-            Span::styled("Enter", Style::default().fg(Color::Green)),
+            Span::styled("Enter", theme.hint_key),
             Span::raw(" next/submit  "),
-            Span::styled("y", Style::default().fg(Color::Green)),
+            Span::styled("y", theme.hint_key),
             Span::raw(" yaml file picker  "),
-            Span::styled("q", Style::default().fg(Color::Green)),
+            Span::styled("Ctrl+p", theme.hint_key),
+            Span::raw(" prompt library  "),
+            Span::styled("q", theme.hint_key),
             Span::raw(" cancel"),
         ])
     } else {
+        let keymap = app.keymap();
+        let key = |action: Action| -> String {
+            let hint = keymap.hint_for(action);
+            if hint.is_empty() { "?".to_string() } else { hint }
+        };
         Line::from(vec![
-            Span::styled("a", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::AddMonitor), theme.hint_key),
             Span::raw(" add  "),
-            Span::styled("e", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::EditMonitor), theme.hint_key),
             Span::raw(" edit  "),
-            Span::styled("d", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::Delete), theme.hint_key),
             Span::raw(" delete  "),
-            Span::styled("s", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::ToggleActivation), theme.hint_key),
             Span::raw(" toggle active  "),
-            Span::styled("r", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::Reconnect), theme.hint_key),
             Span::raw(" reconnect target  "),
-            Span::styled("x", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::TerminateAll), theme.hint_key),
             Span::raw(" kill conns  "),
-            Span::styled("Tab", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::ToggleFocus), theme.hint_key),
             Span::raw(" switch pane  "),
-            Span::styled("Up/Down", Style::default().fg(Color::Green)),
+            Span::styled("Up/Down", theme.hint_key),
             Span::raw(" navigate  "),
-            Span::styled("o", Style::default().fg(Color::Green)),
+            Span::styled(key(Action::OpenUrl), theme.hint_key),
             Span::raw(" open URL  "),
-            Span::styled("c", Style::default().fg(Color::Green)),
+            Span::styled("y/Y", theme.hint_key),
+            Span::raw(" yank (url/text)  "),
+            Span::styled(key(Action::ClearFeed), theme.hint_key),
             Span::raw(" clear feed  "),
-            Span::styled("q", Style::default().fg(Color::Green)),
+            Span::styled("m", theme.hint_key),
+            Span::raw(" mark all seen  "),
+            Span::styled("/", theme.hint_key),
+            Span::raw(" filter  "),
+            Span::styled("E", theme.hint_key),
+            Span::raw(" export to yaml  "),
+            Span::styled(key(Action::Quit), theme.hint_key),
             Span::raw(" quit"),
         ])
     };
@@ -236,12 +333,13 @@ fn render_footer(frame: &mut Frame<'_>, app: &App, area: Rect) {
         Block::default()
             .title("Keyboard")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(theme.border),
     );
     frame.render_widget(footer, area);
 }
 
 fn render_feed(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let theme = &app.theme;
     let wrap_width = area.width.saturating_sub(4) as usize;
     let items = if app.feed.is_empty() {
         let message = if app.monitors.is_empty() {
@@ -253,13 +351,24 @@ fn render_feed(frame: &mut Frame<'_>, app: &App, area: Rect) {
     } else {
         app.feed
             .iter()
-            .map(|event| {
-                let style = match event.kind {
-                    FeedKind::Post { .. } => Style::default().fg(Color::White),
-                    FeedKind::Analysis { .. } => Style::default().fg(Color::LightBlue),
-                    FeedKind::Info(_) => Style::default().fg(Color::Gray),
-                    FeedKind::Error(_) => Style::default().fg(Color::LightRed),
+            .enumerate()
+            .map(|(index, event)| {
+                let mut style = match event.kind {
+                    FeedKind::Post { .. } => theme.feed_post,
+                    FeedKind::Analysis { .. } => theme.feed_analysis,
+                    FeedKind::Info(_) => theme.feed_info,
+                    FeedKind::Error(_) => theme.feed_error,
                 };
+                if event.tracks_seen() {
+                    style = style.patch(if event.seen {
+                        theme.feed_seen
+                    } else {
+                        theme.feed_unseen
+                    });
+                }
+                if index % 2 == 1 {
+                    style = style.patch(theme.feed_zebra);
+                }
 
                 let wrapped = wrap_for_width(&event.summary(), wrap_width)
                     .into_iter()
@@ -287,17 +396,12 @@ fn render_feed(frame: &mut Frame<'_>, app: &App, area: Rect) {
                 .title(title)
                 .borders(Borders::ALL)
                 .border_style(if app.focus == FocusPane::Feed {
-                    Style::default().fg(Color::Cyan)
+                    theme.border_focused
                 } else {
-                    Style::default().fg(Color::DarkGray)
+                    theme.border
                 }),
         )
-        .highlight_style(
-            Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(theme.selection.add_modifier(Modifier::BOLD))
         .highlight_symbol("» ");
 
     frame.render_stateful_widget(list, area, &mut list_state);
@@ -361,20 +465,21 @@ fn push_split_word(word: &str, width: usize, lines: &mut Vec<String>) {
 }
 
 fn render_details(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let theme = &app.theme;
     let text = if app.focus == FocusPane::Monitors {
         if let Some(monitor) = app.selected_monitor() {
             let active = monitor.enabled && app.monitor_is_active(monitor.id);
             let (status_text, status_style) = if !monitor.enabled {
-                ("off", Style::default().fg(Color::Red))
+                ("off", theme.status_inactive)
             } else if active {
-                ("active", Style::default().fg(Color::Green))
+                ("active", theme.status_active)
             } else {
-                ("inactive", Style::default().fg(Color::Red))
+                ("inactive", theme.status_inactive)
             };
             let enabled_style = if monitor.enabled {
-                Style::default().fg(Color::Green)
+                theme.status_active
             } else {
-                Style::default().fg(Color::Red)
+                theme.status_inactive
             };
             vec![
                 // Line::from(format!("Display name: {}", monitor.label)),
@@ -418,17 +523,17 @@ fn render_details(frame: &mut Frame<'_>, app: &App, area: Rect) {
                     "API key: {}",
                     if monitor.analysis.api_key.trim().is_empty() {
                         "(provider default/env)".to_string()
-                    } else if is_env_var_name(monitor.analysis.api_key.trim())
-                        || monitor
-                            .analysis
-                            .api_key
-                            .trim()
-                            .strip_prefix('$')
-                            .is_some_and(is_env_var_name)
-                    {
-                        format!("env ref ({})", monitor.analysis.api_key.trim())
                     } else {
-                        "(monitor override)".to_string()
+                        let secret = crate::secrets::SecretRef::parse(&monitor.analysis.api_key);
+                        if secret.is_opaque_reference() {
+                            format!(
+                                "{} ({})",
+                                secret.label(),
+                                monitor.analysis.api_key.trim()
+                            )
+                        } else {
+                            "(monitor override)".to_string()
+                        }
                     }
                 )),
             ]
@@ -449,23 +554,26 @@ fn render_details(frame: &mut Frame<'_>, app: &App, area: Rect) {
         Block::default()
             .title("Details")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(theme.border),
     );
     frame.render_widget(block, area);
 }
 
 fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
+    let theme = &app.theme;
     let area = centered_rect(70, 70, frame.area());
     frame.render_widget(Clear, area);
     let blink_on = slow_blink_on();
 
     let mut lines = Vec::new();
     lines.push(field_line(
+        theme,
         form.field_index == 0,
         format!("Type: {}", form.kind.display()),
         FieldControl::Toggle,
     ));
     lines.push(field_line(
+        theme,
         form.field_index == 1,
         format!(
             "Target: {}",
@@ -476,16 +584,23 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
     if form.mode == MonitorFormMode::Add {
         lines.push(Line::styled(
             "  YAML target: press 'y' to browse monitor-config files",
-            Style::default().fg(Color::DarkGray),
+            theme.muted,
         ));
     }
     if form.kind == MonitorKind::Account {
         lines.push(Line::styled(
             "  handles: comma-separated, '@' optional",
-            Style::default().fg(Color::DarkGray),
+            theme.muted,
+        ));
+    }
+    if form.kind == MonitorKind::Rss {
+        lines.push(Line::styled(
+            "  feed URL, polled every rss_poll_interval_secs",
+            theme.muted,
         ));
     }
     lines.push(field_line(
+        theme,
         form.field_index == 2,
         format!(
             "Display name: {}",
@@ -494,6 +609,7 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
         FieldControl::Text,
     ));
     lines.push(field_line(
+        theme,
         form.field_index == 3,
         format!(
             "Run AI analysis: {}",
@@ -504,11 +620,13 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
 
     let provider = form.selected_provider(&app.provider_names);
     lines.push(field_line(
+        theme,
         form.field_index == 4,
         format!("AI provider: {provider}"),
         FieldControl::Toggle,
     ));
     lines.push(field_line(
+        theme,
         form.field_index == 5,
         format!(
             "AI model ID: {}",
@@ -517,6 +635,7 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
         FieldControl::Text,
     ));
     lines.push(field_line(
+        theme,
         form.field_index == 6,
         format!(
             "AI endpoint: {}",
@@ -525,6 +644,7 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
         FieldControl::Text,
     ));
     lines.push(field_line(
+        theme,
         form.field_index == 7,
         format!(
             "AI API key: {}",
@@ -536,7 +656,18 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
         ),
         FieldControl::Text,
     ));
+    if form.field_index == 7 {
+        lines.push(Line::styled(
+            "  secret refs: $VAR, keyring:service/account, cmd:..., file:path — plain text is masked",
+            theme.muted,
+        ));
+        lines.push(Line::styled(
+            "  press Ctrl+k to move a pasted key into the OS keyring",
+            theme.muted,
+        ));
+    }
     lines.push(field_line(
+        theme,
         form.field_index == 8,
         format!(
             "AI prompt: {}",
@@ -544,8 +675,33 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
         ),
         FieldControl::Text,
     ));
+    if form.field_index == 8 {
+        lines.push(Line::styled(
+            "  prompt library: press Ctrl+p to browse/save reusable prompts",
+            theme.muted,
+        ));
+    }
     lines.push(field_line(
+        theme,
         form.field_index == 9,
+        format!(
+            "Notify on AI analysis: {}",
+            if form.ai_notify { "Yes" } else { "No" }
+        ),
+        FieldControl::Toggle,
+    ));
+    lines.push(field_line(
+        theme,
+        form.field_index == 10,
+        format!(
+            "Notify on match: {}",
+            if form.notify { "Yes" } else { "No" }
+        ),
+        FieldControl::Toggle,
+    ));
+    lines.push(field_line(
+        theme,
+        form.field_index == 11,
         match form.mode {
             MonitorFormMode::Add => "Create monitor (press Enter)".to_string(),
             MonitorFormMode::Edit => "Save target changes (press Enter)".to_string(),
@@ -562,13 +718,19 @@ fn render_add_modal(frame: &mut Frame<'_>, app: &App, form: &AddMonitorForm) {
         Block::default()
             .title(title)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme.modal_border),
     );
 
     frame.render_widget(modal, area);
 }
 
-fn render_target_file_picker(frame: &mut Frame<'_>, picker: &TargetFilePicker) {
+fn render_target_file_picker(
+    frame: &mut Frame<'_>,
+    app: &App,
+    picker: &TargetFilePicker,
+    highlighted: Option<&[Line<'static>]>,
+) {
+    let theme = &app.theme;
     let area = centered_rect(90, 80, frame.area());
     frame.render_widget(Clear, area);
 
@@ -578,7 +740,7 @@ fn render_target_file_picker(frame: &mut Frame<'_>, picker: &TargetFilePicker) {
             picker.directory.display()
         ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(theme.modal_border);
     frame.render_widget(outer, area);
 
     let inner = area.inner(Margin {
@@ -604,8 +766,8 @@ fn render_target_file_picker(frame: &mut Frame<'_>, picker: &TargetFilePicker) {
             .iter()
             .map(|entry| {
                 let (status, style) = match &entry.parsed {
-                    Ok(_) => ("●", Style::default().fg(Color::Green)),
-                    Err(_) => ("●", Style::default().fg(Color::Red)),
+                    Ok(_) => ("●", theme.picker_valid),
+                    Err(_) => ("●", theme.picker_invalid),
                 };
                 ListItem::new(Line::from(vec![
                     Span::styled(status, style),
@@ -628,21 +790,19 @@ fn render_target_file_picker(frame: &mut Frame<'_>, picker: &TargetFilePicker) {
             Block::default()
                 .title("Files")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+                .border_style(theme.border),
         )
+        .highlight_style(theme.selection.add_modifier(Modifier::BOLD))
         .highlight_symbol("» ");
     frame.render_stateful_widget(list, cols[0], &mut list_state);
 
     let preview_lines = picker
         .entries
         .get(picker.selected)
-        .map(preview_target_file)
+        .map(|entry| {
+            let last_applied = app.applied_target_file_raw(&entry.path);
+            preview_target_file(theme, entry, highlighted, picker.preview_mode, last_applied)
+        })
         .unwrap_or_else(|| vec![Line::from("Select a YAML file from the left list.")]);
 
     let preview = Paragraph::new(preview_lines)
@@ -651,84 +811,501 @@ fn render_target_file_picker(frame: &mut Frame<'_>, picker: &TargetFilePicker) {
             Block::default()
                 .title("Preview")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(theme.border),
         );
     frame.render_widget(preview, cols[1]);
 
     let hints = Paragraph::new(Line::from(vec![
-        Span::styled("Up/Down", Style::default().fg(Color::Green)),
+        Span::styled("Up/Down", theme.hint_key),
         Span::raw(" choose file  "),
-        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::styled("Ctrl+t", theme.hint_key),
+        Span::raw(" cycle preview  "),
+        Span::styled("Enter", theme.hint_key),
         Span::raw(" connect from selected file  "),
-        Span::styled("q", Style::default().fg(Color::Green)),
+        Span::styled("q", theme.hint_key),
         Span::raw(" close"),
     ]));
     frame.render_widget(hints, rows[1]);
 }
 
-fn preview_target_file(entry: &crate::target_files::TargetFileEntry) -> Vec<Line<'static>> {
+fn render_prompt_picker(frame: &mut Frame<'_>, theme: &Theme, picker: &PromptPicker) {
+    let area = centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let outer = Block::default()
+        .title("Prompt Library")
+        .borders(Borders::ALL)
+        .border_style(theme.modal_border);
+    frame.render_widget(outer, area);
+
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(2), Constraint::Length(2)])
+        .split(inner);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    let items = if picker.prompts.is_empty() {
+        vec![ListItem::new(Line::from("No saved prompts yet."))]
+    } else {
+        picker
+            .prompts
+            .iter()
+            .map(|prompt| ListItem::new(Line::from(prompt.name.clone())))
+            .collect::<Vec<_>>()
+    };
+
+    let mut list_state = ListState::default();
+    if !picker.prompts.is_empty() {
+        list_state.select(Some(
+            picker.selected.min(picker.prompts.len().saturating_sub(1)),
+        ));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("Prompts")
+                .borders(Borders::ALL)
+                .border_style(theme.border),
+        )
+        .highlight_style(theme.selection.add_modifier(Modifier::BOLD))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(list, cols[0], &mut list_state);
+
+    let preview_lines = picker
+        .prompts
+        .get(picker.selected)
+        .map(|prompt| vec![Line::from(format!("Name: {}", prompt.name)), Line::from(""), Line::from(prompt.text.clone())])
+        .unwrap_or_else(|| vec![Line::from("Select a prompt from the left list.")]);
+
+    let preview = Paragraph::new(preview_lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title("Preview")
+                .borders(Borders::ALL)
+                .border_style(theme.border),
+        );
+    frame.render_widget(preview, cols[1]);
+
+    if let Some(name) = &picker.save_name_input {
+        let input = Paragraph::new(Line::from(format!("Save current prompt as: {name}_")))
+            .block(
+                Block::default()
+                    .title("Name")
+                    .borders(Borders::ALL)
+                    .border_style(theme.modal_border),
+            );
+        frame.render_widget(input, rows[1]);
+
+        let hints = Paragraph::new(Line::from(vec![
+            Span::styled("type", theme.hint_key),
+            Span::raw(" name  "),
+            Span::styled("Enter", theme.hint_key),
+            Span::raw(" save  "),
+            Span::styled("Esc", theme.hint_key),
+            Span::raw(" cancel"),
+        ]));
+        frame.render_widget(hints, rows[2]);
+        return;
+    }
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled("Up/Down", theme.hint_key),
+        Span::raw(" choose  "),
+        Span::styled("Enter", theme.hint_key),
+        Span::raw(" insert into prompt field  "),
+        Span::styled("s", theme.hint_key),
+        Span::raw(" save current prompt as...  "),
+        Span::styled("q", theme.hint_key),
+        Span::raw(" close"),
+    ]));
+    frame.render_widget(hints, rows[1]);
+}
+
+fn preview_target_file(
+    theme: &Theme,
+    entry: &crate::target_files::TargetFileEntry,
+    highlighted: Option<&[Line<'static>]>,
+    mode: PreviewMode,
+    last_applied: Option<&str>,
+) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     lines.push(Line::from(format!("File: {}", entry.file_name)));
     lines.push(Line::from(format!("Path: {}", entry.path.display())));
+    lines.push(Line::from(format!("Preview: {}", mode.label())));
 
     match &entry.parsed {
-        Ok(target) => {
-            lines.push(Line::from(vec![
-                Span::raw("Status: "),
-                Span::styled("valid", Style::default().fg(Color::Green)),
-            ]));
-            lines.push(Line::from(format!("Kind: {}", target.kind.display())));
-            lines.push(Line::from(format!("Target: {}", target.target)));
-            lines.push(Line::from(format!(
-                "Display name: {}",
-                target.label.clone().unwrap_or_else(|| "(auto)".to_string())
-            )));
-            lines.push(Line::from(format!(
-                "AI: {}",
-                if target.ai_enabled {
-                    "enabled"
-                } else {
-                    "disabled"
-                }
-            )));
-            if let Some(provider) = &target.ai_provider {
-                lines.push(Line::from(format!("AI provider: {provider}")));
-            }
-            if let Some(model) = &target.ai_model {
-                lines.push(Line::from(format!("AI model: {model}")));
-            }
-        }
+        Ok(_) => lines.push(Line::from(vec![
+            Span::raw("Status: "),
+            Span::styled("valid", theme.picker_valid),
+        ])),
         Err(error) => {
             lines.push(Line::from(vec![
                 Span::raw("Status: "),
-                Span::styled("invalid", Style::default().fg(Color::Red)),
+                Span::styled("invalid", theme.picker_invalid),
             ]));
             lines.push(Line::from(format!("Error: {error}")));
         }
     }
-
     lines.push(Line::from(""));
-    lines.push(Line::styled(
-        "YAML contents:",
-        Style::default().fg(Color::Gray),
-    ));
 
+    match mode {
+        PreviewMode::Source => {
+            lines.push(Line::styled("YAML contents:", theme.feed_info));
+            let content_lines = source_content_lines(theme, entry, highlighted);
+            let spans: &[crate::target_files::ErrorSpan] = match &entry.parsed {
+                Err(error) => &error.spans,
+                Ok(_) => &[],
+            };
+            lines.extend(annotate_error_spans(theme, content_lines, spans));
+        }
+        PreviewMode::Summary => {
+            lines.push(Line::styled("Effective settings:", theme.feed_info));
+            lines.extend(target_summary_lines(entry));
+        }
+        PreviewMode::Diff => {
+            lines.push(Line::styled("Diff vs. last applied:", theme.feed_info));
+            lines.extend(target_diff_lines(theme, entry, last_applied));
+        }
+    }
+
+    lines
+}
+
+fn source_content_lines(
+    theme: &Theme,
+    entry: &crate::target_files::TargetFileEntry,
+    highlighted: Option<&[Line<'static>]>,
+) -> Vec<Line<'static>> {
+    if let Some(highlighted) = highlighted {
+        return highlighted.to_vec();
+    }
+    // No tree-sitter grammar/query available (or parsing failed) — fall
+    // back to the hand-rolled line-by-line highlighter.
     let raw = if entry.raw.trim().is_empty() {
         "(empty file)".to_string()
     } else {
         entry.raw.clone()
     };
-    for raw_line in raw.lines() {
-        lines.push(highlight_yaml_line(raw_line));
-    }
+    let mut content_lines = raw
+        .lines()
+        .map(|raw_line| highlight_yaml_line(theme, raw_line))
+        .collect::<Vec<_>>();
     if raw.ends_with('\n') {
-        lines.push(Line::from(""));
+        content_lines.push(Line::from(""));
     }
+    content_lines
+}
+
+fn target_summary_lines(entry: &crate::target_files::TargetFileEntry) -> Vec<Line<'static>> {
+    let Ok(target) = &entry.parsed else {
+        return vec![Line::from(
+            "(file failed to parse — fix the YAML to see effective settings)",
+        )];
+    };
 
+    let mut lines = vec![
+        Line::from(format!("Kind: {}", target.kind.display())),
+        Line::from(format!("Target: {}", target.target)),
+        Line::from(format!(
+            "Display name: {}",
+            target.label.clone().unwrap_or_else(|| "(auto)".to_string())
+        )),
+        Line::from(format!(
+            "AI: {}",
+            if target.ai_enabled { "enabled" } else { "disabled" }
+        )),
+    ];
+    if target.ai_enabled {
+        lines.push(Line::from(format!(
+            "AI provider: {}",
+            target
+                .ai_provider
+                .clone()
+                .unwrap_or_else(|| "(provider default)".to_string())
+        )));
+        lines.push(Line::from(format!(
+            "AI model: {}",
+            target
+                .ai_model
+                .clone()
+                .unwrap_or_else(|| "(provider default)".to_string())
+        )));
+        lines.push(Line::from(format!(
+            "AI endpoint: {}",
+            target
+                .ai_endpoint
+                .clone()
+                .unwrap_or_else(|| "(provider default)".to_string())
+        )));
+        lines.push(Line::from(format!(
+            "Temperature: {}",
+            target
+                .ai_temperature
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "(provider default)".to_string())
+        )));
+        lines.push(Line::from(format!(
+            "Max tokens: {}",
+            target
+                .ai_max_tokens
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "(provider default)".to_string())
+        )));
+        lines.push(Line::from(format!(
+            "Top-p: {}",
+            target
+                .ai_top_p
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "(provider default)".to_string())
+        )));
+    }
     lines
 }
 
-fn highlight_yaml_line(raw_line: &str) -> Line<'static> {
+fn target_diff_lines(
+    theme: &Theme,
+    entry: &crate::target_files::TargetFileEntry,
+    last_applied: Option<&str>,
+) -> Vec<Line<'static>> {
+    let Some(last_applied) = last_applied else {
+        return vec![Line::from(
+            "(no applied version to diff against — select this file once to establish a baseline)",
+        )];
+    };
+
+    if last_applied == entry.raw {
+        return vec![Line::from("(no changes since last applied)")];
+    }
+
+    crate::diff::diff_lines(last_applied, &entry.raw)
+        .into_iter()
+        .map(|diff_line| match diff_line {
+            crate::diff::DiffLine::Unchanged(text) => Line::from(format!("  {text}")),
+            crate::diff::DiffLine::Added(text) => {
+                Line::styled(format!("+ {text}"), theme.picker_valid)
+            }
+            crate::diff::DiffLine::Removed(text) => {
+                Line::styled(format!("- {text}"), theme.picker_invalid)
+            }
+        })
+        .collect()
+}
+
+/// Interleave a `^^^`/`---` marker line beneath every content line that an
+/// error span points at, labeling it with the span's message.
+fn annotate_error_spans(
+    theme: &Theme,
+    content_lines: Vec<Line<'static>>,
+    spans: &[crate::target_files::ErrorSpan],
+) -> Vec<Line<'static>> {
+    if spans.is_empty() {
+        return content_lines;
+    }
+
+    let mut out = Vec::with_capacity(content_lines.len());
+    for (index, line) in content_lines.into_iter().enumerate() {
+        let line_number = index + 1;
+        out.push(line);
+        for span in spans.iter().filter(|span| span.line == line_number) {
+            out.push(error_span_marker_line(theme, span));
+        }
+    }
+    out
+}
+
+fn error_span_marker_line(theme: &Theme, span: &crate::target_files::ErrorSpan) -> Line<'static> {
+    let (marker_char, style) = if span.primary {
+        ("^", theme.picker_invalid)
+    } else {
+        ("-", theme.muted)
+    };
+
+    let mut spans = vec![
+        Span::raw(" ".repeat(span.column.saturating_sub(1))),
+        Span::styled(marker_char.repeat(span.len.max(1)), style),
+    ];
+    if let Some(label) = &span.label {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(label.clone(), style));
+    }
+    Line::from(spans)
+}
+
+fn render_filter_overlay(
+    frame: &mut Frame<'_>,
+    app: &App,
+    pane: FocusPane,
+    query: &str,
+    selected: usize,
+    matches: &[(usize, FuzzyMatch)],
+) {
+    let theme = &app.theme;
+    let area = centered_rect(85, 75, frame.area());
+    frame.render_widget(Clear, area);
+
+    let pane_label = match pane {
+        FocusPane::Monitors => "Monitors",
+        FocusPane::Feed => "Feed",
+    };
+
+    let outer = Block::default()
+        .title(format!("Filter {pane_label}: {query}"))
+        .borders(Borders::ALL)
+        .border_style(theme.modal_border);
+    frame.render_widget(outer, area);
+
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(2)])
+        .split(inner);
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(rows[0]);
+
+    let items = if matches.is_empty() {
+        vec![ListItem::new(Line::from("No matches."))]
+    } else {
+        matches
+            .iter()
+            .map(|(index, fuzzy_match)| {
+                let candidate = app
+                    .filter_candidate_text(pane, *index)
+                    .unwrap_or_default();
+                let spans = highlight_spans(
+                    &candidate,
+                    &fuzzy_match.indices,
+                    Style::default(),
+                    theme.filter_match,
+                );
+                ListItem::new(Line::from(spans))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut list_state = ListState::default();
+    if !matches.is_empty() {
+        list_state.select(Some(selected.min(matches.len().saturating_sub(1))));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("Matches")
+                .borders(Borders::ALL)
+                .border_style(theme.border),
+        )
+        .highlight_style(theme.selection.add_modifier(Modifier::BOLD))
+        .highlight_symbol("» ");
+    frame.render_stateful_widget(list, cols[0], &mut list_state);
+
+    let preview_lines = matches
+        .get(selected)
+        .map(|(index, _)| filter_preview_lines(app, pane, *index))
+        .unwrap_or_else(|| vec![Line::from("Select a match from the left list.")]);
+
+    let preview = Paragraph::new(preview_lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title("Preview")
+                .borders(Borders::ALL)
+                .border_style(theme.border),
+        );
+    frame.render_widget(preview, cols[1]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled("type", theme.hint_key),
+        Span::raw(" to filter  "),
+        Span::styled("Up/Down", theme.hint_key),
+        Span::raw(" choose  "),
+        Span::styled("Enter", theme.hint_key),
+        Span::raw(" jump to selection  "),
+        Span::styled("Esc", theme.hint_key),
+        Span::raw(" close"),
+    ]));
+    frame.render_widget(hints, rows[1]);
+}
+
+/// Split `candidate` into styled runs, applying `highlight` to characters at
+/// `match_indices` (positions into `candidate.chars()`) and `base` elsewhere,
+/// merging consecutive same-style characters into a single span.
+fn highlight_spans(
+    candidate: &str,
+    match_indices: &[usize],
+    base: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    let matched: HashSet<usize> = match_indices.iter().copied().collect();
+
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_style = base;
+
+    for (idx, ch) in candidate.chars().enumerate() {
+        let style = if matched.contains(&idx) { highlight } else { base };
+        if style != current_style && !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut current), current_style));
+        }
+        current_style = style;
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, current_style));
+    }
+
+    spans
+}
+
+/// Detail lines for the match currently selected in the filter overlay: the
+/// same summary/URL lines [`render_details`] shows for a feed item, or a short
+/// name/kind/target/query/AI summary for a monitor.
+fn filter_preview_lines(app: &App, pane: FocusPane, index: usize) -> Vec<Line<'static>> {
+    match pane {
+        FocusPane::Monitors => match app.monitors.get(index) {
+            Some(monitor) => vec![
+                Line::from(format!("Display name: {}", monitor.label)),
+                Line::from(format!("Kind: {}", monitor.kind.display())),
+                Line::from(format!("Target: {}", monitor.input_value)),
+                Line::from(format!("Query: {}", monitor.query)),
+                Line::from(format!(
+                    "AI: {}",
+                    if monitor.analysis.enabled {
+                        format!("enabled ({})", monitor.analysis.provider)
+                    } else {
+                        "disabled".to_string()
+                    }
+                )),
+            ],
+            None => vec![Line::from("Monitor no longer exists.")],
+        },
+        FocusPane::Feed => match app.feed.get(index) {
+            Some(feed) => {
+                let mut lines = vec![Line::from(feed.summary())];
+                if let Some(url) = &feed.url {
+                    lines.push(Line::from(format!("URL: {url}")));
+                }
+                lines
+            }
+            None => vec![Line::from("Feed item no longer exists.")],
+        },
+    }
+}
+
+fn highlight_yaml_line(theme: &Theme, raw_line: &str) -> Line<'static> {
     if raw_line.is_empty() {
         return Line::from(String::new());
     }
@@ -742,17 +1319,11 @@ fn highlight_yaml_line(raw_line: &str) -> Line<'static> {
 
     let mut spans = Vec::new();
     if !indent.is_empty() {
-        spans.push(Span::styled(
-            indent.to_string(),
-            Style::default().fg(Color::DarkGray),
-        ));
+        spans.push(Span::styled(indent.to_string(), theme.muted));
     }
-    spans.extend(highlight_yaml_content(content));
+    spans.extend(highlight_yaml_content(theme, content));
     if let Some(comment) = comment {
-        spans.push(Span::styled(
-            comment.to_string(),
-            Style::default().fg(Color::DarkGray),
-        ));
+        spans.push(Span::styled(comment.to_string(), theme.yaml_comment));
     }
 
     if spans.is_empty() {
@@ -776,7 +1347,7 @@ fn split_yaml_comment(body: &str) -> (&str, Option<&str>) {
     (body, None)
 }
 
-fn highlight_yaml_content(content: &str) -> Vec<Span<'static>> {
+fn highlight_yaml_content(theme: &Theme, content: &str) -> Vec<Span<'static>> {
     if content.is_empty() {
         return vec![];
     }
@@ -789,40 +1360,27 @@ fn highlight_yaml_content(content: &str) -> Vec<Span<'static>> {
     let rest = &content[leading_ws_len..];
 
     if !leading_ws.is_empty() {
-        spans.push(Span::styled(
-            leading_ws.to_string(),
-            Style::default().fg(Color::DarkGray),
-        ));
+        spans.push(Span::styled(leading_ws.to_string(), theme.muted));
     }
 
     if rest.starts_with("- ") {
-        spans.push(Span::styled(
-            "-".to_string(),
-            Style::default()
-                .fg(Color::LightMagenta)
-                .add_modifier(Modifier::BOLD),
-        ));
+        spans.push(Span::styled("-".to_string(), theme.yaml_list_marker));
         spans.push(Span::raw(" "));
-        spans.extend(highlight_yaml_mapping_or_scalar(&rest[2..]));
+        spans.extend(highlight_yaml_mapping_or_scalar(theme, &rest[2..]));
         return spans;
     }
 
-    spans.extend(highlight_yaml_mapping_or_scalar(rest));
+    spans.extend(highlight_yaml_mapping_or_scalar(theme, rest));
     spans
 }
 
-fn highlight_yaml_mapping_or_scalar(text: &str) -> Vec<Span<'static>> {
+fn highlight_yaml_mapping_or_scalar(theme: &Theme, text: &str) -> Vec<Span<'static>> {
     if let Some(colon_idx) = find_unquoted_colon(text) {
         let key = &text[..colon_idx];
         let tail = &text[colon_idx + 1..];
         let mut spans = vec![
-            Span::styled(
-                key.to_string(),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(":".to_string(), Style::default().fg(Color::DarkGray)),
+            Span::styled(key.to_string(), theme.yaml_key),
+            Span::styled(":".to_string(), theme.yaml_punctuation),
         ];
 
         let tail_ws_len = tail
@@ -835,12 +1393,12 @@ fn highlight_yaml_mapping_or_scalar(text: &str) -> Vec<Span<'static>> {
             spans.push(Span::raw(tail_ws.to_string()));
         }
         if !value.is_empty() {
-            spans.push(Span::styled(value.to_string(), yaml_value_style(value)));
+            spans.push(Span::styled(value.to_string(), yaml_value_style(theme, value)));
         }
 
         spans
     } else {
-        vec![Span::styled(text.to_string(), yaml_value_style(text))]
+        vec![Span::styled(text.to_string(), yaml_value_style(theme, text))]
     }
 }
 
@@ -858,10 +1416,10 @@ fn find_unquoted_colon(input: &str) -> Option<usize> {
     None
 }
 
-fn yaml_value_style(value: &str) -> Style {
+fn yaml_value_style(theme: &Theme, value: &str) -> Style {
     let trimmed = value.trim();
     if trimmed.is_empty() {
-        return Style::default().fg(Color::White);
+        return theme.yaml_plain;
     }
 
     let bool_like = matches!(
@@ -869,27 +1427,27 @@ fn yaml_value_style(value: &str) -> Style {
         "true" | "false" | "yes" | "no" | "on" | "off"
     );
     if bool_like {
-        return Style::default().fg(Color::Green);
+        return theme.yaml_bool;
     }
 
     let null_like = matches!(trimmed.to_ascii_lowercase().as_str(), "null" | "~");
     if null_like {
-        return Style::default().fg(Color::Gray);
+        return theme.yaml_null;
     }
 
     if trimmed.parse::<f64>().is_ok() {
-        return Style::default().fg(Color::LightMagenta);
+        return theme.yaml_number;
     }
 
     if trimmed.starts_with('"') || trimmed.starts_with('\'') {
-        return Style::default().fg(Color::Yellow);
+        return theme.yaml_string;
     }
 
     if trimmed.starts_with('&') || trimmed.starts_with('*') {
-        return Style::default().fg(Color::LightCyan);
+        return theme.yaml_anchor;
     }
 
-    Style::default().fg(Color::White)
+    theme.yaml_plain
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -899,16 +1457,11 @@ enum FieldControl {
     Submit,
 }
 
-fn field_line(selected: bool, text: String, control: FieldControl) -> Line<'static> {
+fn field_line(theme: &Theme, selected: bool, text: String, control: FieldControl) -> Line<'static> {
     if selected {
         let mut spans = vec![
-            Span::styled(
-                "> ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(text, Style::default().fg(Color::Yellow)),
+            Span::styled("> ", theme.field_selected.add_modifier(Modifier::BOLD)),
+            Span::styled(text, theme.field_selected),
         ];
 
         let hint = match control {
@@ -917,7 +1470,7 @@ fn field_line(selected: bool, text: String, control: FieldControl) -> Line<'stat
             FieldControl::Submit => "[Enter]",
         };
         spans.push(Span::raw("  "));
-        spans.push(Span::styled(hint, Style::default().fg(Color::Green)));
+        spans.push(Span::styled(hint, theme.field_hint));
 
         Line::from(spans)
     } else {
@@ -933,35 +1486,14 @@ fn with_blink_cursor(input: &str, active: bool, blink_on: bool) -> String {
     format!("{input}{cursor}")
 }
 
-fn mask_secret(input: &str) -> String {
-    if input.is_empty() {
-        return String::new();
-    }
-    "*".repeat(input.chars().count())
-}
-
+/// Display form for an API key field: opaque secret references (env var,
+/// `keyring:`/`cmd:`/`file:`) show as-is, pasted literals show masked.
 fn api_key_input_display(input: &str) -> String {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return String::new();
     }
-
-    if let Some(var_name) = trimmed.strip_prefix('$') {
-        if is_env_var_name(var_name) {
-            return trimmed.to_string();
-        }
-    } else if is_env_var_name(trimmed) {
-        return trimmed.to_string();
-    }
-
-    mask_secret(trimmed)
-}
-
-fn is_env_var_name(name: &str) -> bool {
-    !name.is_empty()
-        && name
-            .chars()
-            .all(|ch| ch == '_' || ch.is_ascii_uppercase() || ch.is_ascii_digit())
+    crate::secrets::SecretRef::parse(trimmed).display(trimmed)
 }
 
 fn slow_blink_on() -> bool {
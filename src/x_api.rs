@@ -11,12 +11,21 @@ use tokio::{
 };
 use tokio_util::io::StreamReader;
 
-use crate::{AppMsg, models::StreamPost};
+use crate::{AppMsg, ai::ToolRegistry, models::StreamPost};
 
 const API_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 const STREAM_CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
 const STREAM_TCP_KEEPALIVE: Duration = Duration::from_secs(30);
 
+/// Reconnect policy for [`XApiClient::stream_loop`]'s generic-error backoff,
+/// sourced from `AppConfig`. `max_attempts == 0` means retry forever.
+#[derive(Debug, Clone, Copy)]
+pub struct StreamReconnectConfig {
+    pub base_secs: u64,
+    pub cap_secs: u64,
+    pub max_attempts: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct XApiClient {
     http: reqwest::Client,
@@ -138,6 +147,60 @@ impl XApiClient {
         Ok(Self { http })
     }
 
+    /// Fetch a tweet by id (the conversation parent, typically) as raw JSON so
+    /// the analyst can reason about the post a reply is responding to.
+    pub async fn fetch_tweet(&self, tweet_id: &str) -> Result<serde_json::Value> {
+        let response = self
+            .http
+            .get(format!("https://api.x.com/2/tweets/{tweet_id}"))
+            .query(&[
+                ("tweet.fields", "author_id,created_at,conversation_id"),
+                ("expansions", "author_id,referenced_tweets.id"),
+                ("user.fields", "username"),
+            ])
+            .timeout(API_REQUEST_TIMEOUT)
+            .send()
+            .await
+            .context("failed to call fetch tweet endpoint")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("fetch tweet failed ({status}): {body}");
+        }
+
+        response
+            .json::<serde_json::Value>()
+            .await
+            .context("failed to parse fetch tweet response")
+    }
+
+    /// Fetch an author's recent posts as raw JSON, keyed by numeric author id.
+    pub async fn fetch_author_timeline(&self, author_id: &str) -> Result<serde_json::Value> {
+        let response = self
+            .http
+            .get(format!("https://api.x.com/2/users/{author_id}/tweets"))
+            .query(&[
+                ("max_results", "10"),
+                ("tweet.fields", "created_at,conversation_id"),
+            ])
+            .timeout(API_REQUEST_TIMEOUT)
+            .send()
+            .await
+            .context("failed to call author timeline endpoint")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("author timeline failed ({status}): {body}");
+        }
+
+        response
+            .json::<serde_json::Value>()
+            .await
+            .context("failed to parse author timeline response")
+    }
+
     pub async fn add_rule(&self, value: String, tag: String) -> Result<String> {
         let body = AddRuleBody {
             add: vec![AddRule { value, tag }],
@@ -320,11 +383,12 @@ impl XApiClient {
         self,
         tx: UnboundedSender<AppMsg>,
         mut shutdown_rx: watch::Receiver<bool>,
+        reconnect: StreamReconnectConfig,
     ) {
-        let mut retry_seconds = 2u64;
         let mut missing_rules_message_sent = false;
         let mut provisioning_message_sent = false;
         let mut too_many_connections_message_sent = false;
+        let mut consecutive_failures = 0u32;
 
         loop {
             if *shutdown_rx.borrow() {
@@ -333,7 +397,10 @@ impl XApiClient {
 
             let _ = tx.send(AppMsg::Info("connecting to filtered stream".to_string()));
 
-            match self.stream_once(&tx, &mut shutdown_rx).await {
+            match self
+                .stream_once(&tx, &mut shutdown_rx, &mut consecutive_failures)
+                .await
+            {
                 Ok(()) => {
                     let _ = tx.send(AppMsg::StreamConnectionState(false));
                     let _ = tx.send(AppMsg::Info("stream stopped".to_string()));
@@ -389,7 +456,6 @@ impl XApiClient {
                         }
                         missing_rules_message_sent = false;
                         provisioning_message_sent = false;
-                        retry_seconds = 60;
                         tokio::select! {
                             _ = shutdown_rx.changed() => {
                                 break;
@@ -399,20 +465,30 @@ impl XApiClient {
                         continue;
                     }
 
+                    consecutive_failures = consecutive_failures.saturating_add(1);
+                    if reconnect.max_attempts != 0 && consecutive_failures > reconnect.max_attempts
+                    {
+                        let _ = tx.send(AppMsg::Error(format!(
+                            "stream disconnected: {error}; giving up after {consecutive_failures} attempts"
+                        )));
+                        break;
+                    }
+
+                    let delay = backoff_delay(&reconnect, consecutive_failures);
                     missing_rules_message_sent = false;
                     provisioning_message_sent = false;
                     too_many_connections_message_sent = false;
                     let _ = tx.send(AppMsg::Error(format!("stream disconnected: {error}")));
                     let _ = tx.send(AppMsg::Info(format!(
-                        "retrying stream connection in {retry_seconds}s"
+                        "reconnecting in {}s, attempt {consecutive_failures}",
+                        delay.as_secs()
                     )));
                     tokio::select! {
                         _ = shutdown_rx.changed() => {
                             break;
                         }
-                        _ = sleep(Duration::from_secs(retry_seconds)) => {}
+                        _ = sleep(delay) => {}
                     }
-                    retry_seconds = (retry_seconds * 2).min(60);
                 }
             }
         }
@@ -422,6 +498,7 @@ impl XApiClient {
         &self,
         tx: &UnboundedSender<AppMsg>,
         shutdown_rx: &mut watch::Receiver<bool>,
+        consecutive_failures: &mut u32,
     ) -> Result<()> {
         let response = self
             .http
@@ -474,6 +551,7 @@ impl XApiClient {
         let reader = StreamReader::new(stream);
         let mut lines = BufReader::new(reader).lines();
 
+        *consecutive_failures = 0;
         let _ = tx.send(AppMsg::StreamConnectionState(true));
         let _ = tx.send(AppMsg::Info("stream connected".to_string()));
 
@@ -496,6 +574,118 @@ impl XApiClient {
     }
 }
 
+/// Tool registry backing AI function-calling: lets the analyst fetch the parent
+/// tweet of a reply or the author's recent timeline before answering. Bound to
+/// a single post so tool arguments can default to that post's ids.
+#[derive(Debug, Clone)]
+pub struct XToolRegistry {
+    client: XApiClient,
+    tweet_id: String,
+    author_id: Option<String>,
+    author_username: Option<String>,
+}
+
+impl XToolRegistry {
+    pub fn new(client: XApiClient, post: &StreamPost) -> Self {
+        Self {
+            client,
+            tweet_id: post.id.clone(),
+            author_id: post.author_id.clone(),
+            author_username: post.author_username.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FetchThreadArgs {
+    #[serde(default)]
+    tweet_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FetchTimelineArgs {
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    author_id: Option<String>,
+}
+
+impl ToolRegistry for XToolRegistry {
+    fn definitions(&self) -> Vec<serde_json::Value> {
+        vec![
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": "fetch_thread",
+                    "description": "Fetch the parent/conversation tweet this post is replying to.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "tweet_id": {
+                                "type": "string",
+                                "description": "Tweet id to fetch. Defaults to the matched post."
+                            }
+                        }
+                    }
+                }
+            }),
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": "fetch_author_timeline",
+                    "description": "Fetch the recent posts of the author of this post.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "username": {"type": "string"},
+                            "author_id": {"type": "string"}
+                        }
+                    }
+                }
+            }),
+        ]
+    }
+
+    async fn dispatch(&self, name: &str, arguments: &str) -> Result<String> {
+        let value = match name {
+            "fetch_thread" => {
+                let args: FetchThreadArgs =
+                    serde_json::from_str(arguments).unwrap_or(FetchThreadArgs { tweet_id: None });
+                let tweet_id = args
+                    .tweet_id
+                    .filter(|id| !id.trim().is_empty())
+                    .unwrap_or_else(|| self.tweet_id.clone());
+                self.client.fetch_tweet(&tweet_id).await?
+            }
+            "fetch_author_timeline" => {
+                let args: FetchTimelineArgs = serde_json::from_str(arguments).unwrap_or(
+                    FetchTimelineArgs {
+                        username: None,
+                        author_id: None,
+                    },
+                );
+                let author_id = args
+                    .author_id
+                    .filter(|id| !id.trim().is_empty())
+                    .or_else(|| self.author_id.clone());
+                let Some(author_id) = author_id else {
+                    // We only have the numeric id path; surface the handle so the
+                    // model knows why the lookup could not run.
+                    let username = args
+                        .username
+                        .or_else(|| self.author_username.clone())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    anyhow::bail!("no author_id available for @{username}");
+                };
+                self.client.fetch_author_timeline(&author_id).await?
+            }
+            other => anyhow::bail!("unknown tool '{other}'"),
+        };
+
+        serde_json::to_string(&value).context("failed to serialize tool result")
+    }
+}
+
 fn handle_stream_line(tx: &UnboundedSender<AppMsg>, line: &str) -> Result<()> {
     let parsed: StreamEnvelope = serde_json::from_str(line)
         .with_context(|| format!("failed to parse stream message: {line}"))?;
@@ -533,6 +723,7 @@ fn handle_stream_line(tx: &UnboundedSender<AppMsg>, line: &str) -> Result<()> {
             author_username,
             text: data.text,
             matching_tags,
+            url: None,
         };
         let _ = tx.send(AppMsg::StreamPost(post));
     }
@@ -540,6 +731,26 @@ fn handle_stream_line(tx: &UnboundedSender<AppMsg>, line: &str) -> Result<()> {
     Ok(())
 }
 
+/// Exponential backoff with a small additive jitter so a flapping connection
+/// doesn't hammer the API on a fixed cadence; capped at `reconnect.cap_secs`.
+fn backoff_delay(reconnect: &StreamReconnectConfig, attempt: u32) -> Duration {
+    let base = Duration::from_secs(reconnect.base_secs.max(1));
+    let cap = Duration::from_secs(reconnect.cap_secs.max(reconnect.base_secs.max(1)));
+    let scaled = base.saturating_mul(1u32 << attempt.saturating_sub(1).min(16));
+    (scaled + jitter(base)).min(cap)
+}
+
+/// Up to ~`base`/4 of pseudo-random jitter derived from the wall clock; avoids
+/// pulling in an RNG dependency for a non-cryptographic nudge.
+fn jitter(base: Duration) -> Duration {
+    let span = (base.as_millis() / 4).max(1) as u64;
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos() as u64)
+        .unwrap_or(0);
+    Duration::from_millis(nanos % span)
+}
+
 fn format_errors(errors: &[ApiError]) -> String {
     errors
         .iter()
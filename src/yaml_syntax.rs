@@ -0,0 +1,129 @@
+//! Real YAML syntax highlighting for the target-file picker's raw preview,
+//! via `tree-sitter` + the `tree-sitter-yaml` grammar. Parses the whole
+//! document once into a syntax tree, runs a `highlights.scm` query over it to
+//! assign capture names to byte ranges, then maps those captures onto the
+//! active [`Theme`]'s YAML scopes and slices the result back into per-line
+//! ratatui [`Line`]s. Callers are expected to cache the returned lines
+//! per-entry (keyed on file mtime) so scrolling doesn't re-parse; see
+//! `App::target_file_preview_highlight`.
+
+use std::sync::OnceLock;
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+
+use crate::theme::Theme;
+
+const HIGHLIGHTS_QUERY: &str = include_str!("yaml_highlights.scm");
+
+fn query() -> &'static Query {
+    static QUERY: OnceLock<Query> = OnceLock::new();
+    QUERY.get_or_init(|| {
+        Query::new(&tree_sitter_yaml::LANGUAGE.into(), HIGHLIGHTS_QUERY)
+            .expect("bundled yaml_highlights.scm must compile against tree-sitter-yaml")
+    })
+}
+
+/// Tokenize `raw` as YAML and return one ratatui [`Line`] per input line,
+/// with spans styled from the active theme's YAML scopes. Returns `None` if
+/// the document fails to parse or the grammar is unavailable — a highlighting
+/// failure should never be fatal, callers fall back to a hand-rolled
+/// line-by-line highlighter on `None`.
+pub fn highlight_yaml(theme: &Theme, raw: &str) -> Option<Vec<Line<'static>>> {
+    let mut parser = Parser::new();
+    parser.set_language(&tree_sitter_yaml::LANGUAGE.into()).ok()?;
+    let tree = parser.parse(raw, None)?;
+
+    let mut ranges: Vec<(usize, usize, Style)> = Vec::new();
+    let query = query();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), raw.as_bytes());
+    while let Some(m) = matches.next() {
+        for capture in m.captures {
+            let name = query.capture_names()[capture.index as usize];
+            let style = capture_style(theme, name);
+            let node = capture.node;
+            ranges.push((node.start_byte(), node.end_byte(), style));
+        }
+    }
+    // Later-matched (usually more specific) captures win on overlap, so sort
+    // by start and let `lines_from_ranges` apply them in order.
+    ranges.sort_by_key(|(start, ..)| *start);
+
+    Some(lines_from_ranges(raw, &ranges))
+}
+
+fn capture_style(theme: &Theme, capture_name: &str) -> Style {
+    match capture_name {
+        "property" => theme.yaml_key,
+        "punctuation.delimiter" => theme.yaml_punctuation,
+        "punctuation.special" => theme.yaml_list_marker,
+        "boolean" => theme.yaml_bool,
+        "constant.builtin" => theme.yaml_null,
+        "number" => theme.yaml_number,
+        "string" => theme.yaml_string,
+        "label" => theme.yaml_anchor,
+        "tag" => theme.yaml_anchor,
+        "comment" => theme.yaml_comment,
+        _ => theme.yaml_plain,
+    }
+}
+
+/// Slice `raw` into per-line spans, applying the (possibly overlapping) style
+/// ranges in the order given — later ranges patch over earlier ones, so a
+/// narrower/more specific capture drawn after a broader one still shows.
+fn lines_from_ranges(raw: &str, ranges: &[(usize, usize, Style)]) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+
+    for (idx, byte) in raw.bytes().enumerate() {
+        if byte != b'\n' {
+            continue;
+        }
+        lines.push(spans_for_line(raw, line_start, idx, ranges));
+        line_start = idx + 1;
+    }
+    if line_start <= raw.len() {
+        lines.push(spans_for_line(raw, line_start, raw.len(), ranges));
+    }
+
+    lines
+}
+
+fn spans_for_line(raw: &str, line_start: usize, line_end: usize, ranges: &[(usize, usize, Style)]) -> Line<'static> {
+    let text = raw[line_start..line_end].trim_end_matches('\r');
+    if text.is_empty() {
+        return Line::from(String::new());
+    }
+
+    let mut boundaries = vec![line_start, line_start + text.len()];
+    for (start, end, _) in ranges {
+        if *end <= line_start || *start >= line_start + text.len() {
+            continue;
+        }
+        boundaries.push((*start).clamp(line_start, line_start + text.len()));
+        boundaries.push((*end).clamp(line_start, line_start + text.len()));
+    }
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut spans = Vec::new();
+    for window in boundaries.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if start >= end {
+            continue;
+        }
+        let style = ranges
+            .iter()
+            .filter(|(range_start, range_end, _)| *range_start <= start && end <= *range_end)
+            .last()
+            .map(|(_, _, style)| *style)
+            .unwrap_or_default();
+        spans.push(Span::styled(raw[start..end].to_string(), style));
+    }
+
+    Line::from(spans)
+}